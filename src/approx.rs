@@ -0,0 +1,82 @@
+use crate::{Float, GlobalMercator};
+
+/// Tolerance of `approx_eq_meters`: one centimeter.
+///
+/// Web Mercator coordinates reach 2e7 meters, where f64 still resolves a few nanometers, so
+/// anything beyond this is a real difference rather than a rounding error.
+pub const METERS_TOLERANCE: f64 = 0.01;
+
+/// Tolerance of `approx_eq_deg`: 1e-7 degrees, about a centimeter on the equator.
+pub const DEGREES_TOLERANCE: f64 = 1e-7;
+
+/// Compares two EPSG:900913 coordinates within `METERS_TOLERANCE`.
+pub fn approx_eq_meters(a: f64, b: f64) -> bool {
+    Float::abs(a - b) <= METERS_TOLERANCE
+}
+
+/// Compares two latitudes or longitudes within `DEGREES_TOLERANCE`.
+pub fn approx_eq_deg(a: f64, b: f64) -> bool {
+    Float::abs(a - b) <= DEGREES_TOLERANCE
+}
+
+impl<T: Float> GlobalMercator<T> {
+    /// Returns `true` if both pyramids have the same tile size, pixel ratio and origin, and
+    /// radii and derived resolutions within `epsilon` of each other, relative to their magnitude.
+    ///
+    /// The zoom cache is ignored: it changes how resolutions are computed, not their value.
+    pub fn approx_eq(&self, other: &GlobalMercator<T>, epsilon: T) -> bool {
+        let close = |a: T, b: T| {
+            let scale = if a.abs() > b.abs() { a.abs() } else { b.abs() };
+            (a - b).abs() <= epsilon * scale
+        };
+
+        self.tile_size == other.tile_size
+            && self.pixel_ratio == other.pixel_ratio
+            && self.origin == other.origin
+            && close(self.radius, other.radius)
+            && close(self.initial_resolution, other.initial_resolution)
+            && close(self.origin_shift, other.origin_shift)
+    }
+}
+
+/// Compares with `approx_eq` and a few ulps of tolerance, so pyramids built through different
+/// constructors compare equal despite rounding.
+impl<T: Float> PartialEq for GlobalMercator<T> {
+    fn eq(&self, other: &GlobalMercator<T>) -> bool {
+        self.approx_eq(other, T::EPSILON * T::from_f64(4.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_approx_eq_meters() {
+        assert!(approx_eq_meters(20037508.342789244, 20037508.343789244));
+        assert!(approx_eq_meters(-1.0, -1.001));
+        assert!(!approx_eq_meters(0.0, 0.1));
+    }
+
+    #[test]
+    fn test_approx_eq_deg() {
+        assert!(approx_eq_deg(85.05112878, 85.05112878 + 1e-9));
+        assert!(!approx_eq_deg(48.6, 49.6));
+        assert!(!approx_eq_deg(0.0, 1e-6));
+    }
+
+    #[test]
+    fn test_mercator_eq() {
+        assert_eq!(GlobalMercator::new(256), GlobalMercator::with_radius(256, 6378137.0));
+        assert_eq!(GlobalMercator::new(256), GlobalMercator::with_zoom_cache(256, 20));
+        assert_ne!(GlobalMercator::new(256), GlobalMercator::new(512));
+        assert_ne!(GlobalMercator::new(256), GlobalMercator::new(256).with_origin(crate::Origin::TopLeft));
+        assert_ne!(GlobalMercator::new(512), GlobalMercator::with_dpi_scale(256, 2));
+
+        let sphere = GlobalMercator::with_radius(256, 6371000.0);
+        assert_ne!(GlobalMercator::new(256), sphere);
+        assert!(GlobalMercator::new(256).approx_eq(&sphere, 1e-2));
+        assert!(!GlobalMercator::new(256).approx_eq(&sphere, 1e-3));
+        assert_eq!(GlobalMercator::<f32>::with_tile_size(256), GlobalMercator::<f32>::with_radius(256, 6378137.0));
+    }
+}
@@ -0,0 +1,105 @@
+use core::fmt;
+
+use crate::Float;
+
+/// An axis aligned extent.
+///
+/// Holds EPSG:900913 meters when produced by `GlobalMercator::tile_bounds_struct`, or degrees
+/// with `x` as longitude and `y` as latitude when produced by
+/// `GlobalMercator::tile_lat_lon_bounds_struct`. Edges are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bounds<T = f64> {
+    pub min_x: T,
+    pub min_y: T,
+    pub max_x: T,
+    pub max_y: T,
+}
+
+impl<T: Float> Bounds<T> {
+    pub fn new(min_x: T, min_y: T, max_x: T, max_y: T) -> Bounds<T> {
+        Bounds { min_x, min_y, max_x, max_y }
+    }
+
+    pub fn width(&self) -> T {
+        self.max_x - self.min_x
+    }
+
+    pub fn height(&self) -> T {
+        self.max_y - self.min_y
+    }
+
+    pub fn center(&self) -> (T, T) {
+        let two = T::from_f64(2.0);
+        ((self.min_x + self.max_x) / two, (self.min_y + self.max_y) / two)
+    }
+
+    pub fn contains(&self, x: T, y: T) -> bool {
+        self.min_x <= x && x <= self.max_x && self.min_y <= y && y <= self.max_y
+    }
+
+    /// Returns `true` if the two extents overlap or share an edge.
+    pub fn intersects(&self, other: &Bounds<T>) -> bool {
+        self.min_x <= other.max_x && other.min_x <= self.max_x && self.min_y <= other.max_y && other.min_y <= self.max_y
+    }
+}
+
+/// Formats the extent as `[min_x, min_y, max_x, max_y]`.
+impl<T: fmt::Display> fmt::Display for Bounds<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}, {}, {}, {}]", self.min_x, self.min_y, self.max_x, self.max_y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GlobalMercator;
+
+    #[test]
+    fn test_dimensions() {
+        let bounds = Bounds::new(-1.0, 2.0, 3.0, 8.0);
+        assert_eq!(bounds.width(), 4.0);
+        assert_eq!(bounds.height(), 6.0);
+        assert_eq!(bounds.center(), (1.0, 5.0));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Bounds::new(-1.0, 2.0, 3.5, 8.0).to_string(), "[-1, 2, 3.5, 8]");
+    }
+
+    #[test]
+    fn test_straddling_origin() {
+        let mercator = GlobalMercator::default();
+
+        // The single zoom 0 tile straddles the origin
+        let world = mercator.tile_bounds_struct(0, 0, 0);
+        assert_eq!(world.center(), (0.0, 0.0));
+        assert!(world.contains(0.0, 0.0));
+        assert!(world.contains(world.max_x, world.min_y));
+        assert!(!world.contains(world.max_x + 1.0, 0.0));
+
+        // All four zoom 1 tiles meet at the origin
+        let south_west = mercator.tile_bounds_struct(0, 0, 1);
+        let north_east = mercator.tile_bounds_struct(1, 1, 1);
+        assert!(south_west.contains(0.0, 0.0));
+        assert!(north_east.contains(0.0, 0.0));
+        assert!(south_west.intersects(&north_east));
+        assert!(world.intersects(&south_west));
+        assert!(!south_west.intersects(&mercator.tile_bounds_struct(3, 3, 2)));
+    }
+
+    #[test]
+    fn test_matches_tuples() {
+        let mercator = GlobalMercator::default();
+
+        let (minx, miny, maxx, maxy) = mercator.tile_bounds(134, 90, 8);
+        assert_eq!(mercator.tile_bounds_struct(134, 90, 8), Bounds::new(minx, miny, maxx, maxy));
+        assert_eq!(mercator.tile_bounds_array(134, 90, 8), [[minx, miny], [maxx, maxy]]);
+
+        let (min_lat, min_lon, max_lat, max_lon) = mercator.tile_lat_lon_bounds(134, 90, 8);
+        assert_eq!(mercator.tile_lat_lon_bounds_struct(134, 90, 8), Bounds::new(min_lon, min_lat, max_lon, max_lat));
+        assert_eq!(mercator.tile_lat_lon_bounds_array(134, 90, 8), [[min_lat, min_lon], [max_lat, max_lon]]);
+    }
+}
@@ -0,0 +1,149 @@
+use core::cmp::Ordering;
+
+use crate::{Float, GlobalMercator, GlobalMercatorError, Origin, WGS84_RADIUS};
+
+/// Step by step configuration of a `GlobalMercator`, validated by `build`.
+///
+/// ```
+/// use globalmaptiles::{GlobalMercatorBuilder, Origin};
+///
+/// let mercator = GlobalMercatorBuilder::new().tile_size(512).origin(Origin::TopLeft).build().unwrap();
+/// assert_eq!(mercator.tile_size(), 512);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct GlobalMercatorBuilder<T = f64> {
+    tile_size: u32,
+    radius: T,
+    origin: Origin,
+    max_cached_zoom: Option<u32>,
+    dpi_scale: u32,
+}
+
+/// Starts from the defaults of `GlobalMercator::default()`: 256 pixel tiles on the WGS84 sphere
+/// with TMS rows. `GlobalMercatorBuilder::<f32>::default()` builds an `f32` pyramid.
+impl<T: Float> Default for GlobalMercatorBuilder<T> {
+    fn default() -> Self {
+        GlobalMercatorBuilder {
+            tile_size: 256,
+            radius: T::from_f64(WGS84_RADIUS),
+            origin: Origin::BottomLeft,
+            max_cached_zoom: None,
+            dpi_scale: 1,
+        }
+    }
+}
+
+impl GlobalMercatorBuilder {
+    /// Same as `default`, for `f64`.
+    pub fn new() -> GlobalMercatorBuilder {
+        GlobalMercatorBuilder::default()
+    }
+}
+
+impl<T: Float> GlobalMercatorBuilder<T> {
+    /// Logical tile size in pixels, see `GlobalMercator::with_tile_size`.
+    pub fn tile_size(mut self, tile_size: u32) -> GlobalMercatorBuilder<T> {
+        self.tile_size = tile_size;
+        self
+    }
+
+    /// Sphere radius in meters, see `GlobalMercator::with_radius`.
+    pub fn radius(mut self, radius: T) -> GlobalMercatorBuilder<T> {
+        self.radius = radius;
+        self
+    }
+
+    /// Corner tile rows are counted from, see `GlobalMercator::with_origin`.
+    pub fn origin(mut self, origin: Origin) -> GlobalMercatorBuilder<T> {
+        self.origin = origin;
+        self
+    }
+
+    /// Precomputes the resolutions of zooms `0..=max_zoom`, see `GlobalMercator::with_zoom_cache`.
+    pub fn zoom_cache(mut self, max_zoom: u32) -> GlobalMercatorBuilder<T> {
+        self.max_cached_zoom = Some(max_zoom);
+        self
+    }
+
+    /// Image pixels per logical pixel, see `GlobalMercator::with_dpi_scale`.
+    pub fn dpi_scale(mut self, scale: u32) -> GlobalMercatorBuilder<T> {
+        self.dpi_scale = scale;
+        self
+    }
+
+    /// Returns the configured pyramid, or an error for a zero tile size or DPI scale, or a radius
+    /// that isn't a positive finite number.
+    pub fn build(self) -> Result<GlobalMercator<T>, GlobalMercatorError> {
+        if self.tile_size == 0 {
+            return Err(GlobalMercatorError::InvalidTileSize(self.tile_size));
+        }
+        if self.dpi_scale == 0 {
+            return Err(GlobalMercatorError::InvalidDpiScale(self.dpi_scale));
+        }
+        if self.radius.partial_cmp(&T::from_f64(0.0)) != Some(Ordering::Greater) || !self.radius.to_f64().is_finite() {
+            return Err(GlobalMercatorError::InvalidRadius(self.radius.to_f64()));
+        }
+
+        let mut mercator = GlobalMercator::with_radius(self.tile_size, self.radius).with_origin(self.origin);
+        mercator.pixel_ratio = self.dpi_scale;
+        if let Some(max_zoom) = self.max_cached_zoom {
+            mercator = mercator.zoom_cache(max_zoom);
+        }
+        Ok(mercator)
+    }
+}
+
+impl GlobalMercator {
+    /// Same as `GlobalMercatorBuilder::new`.
+    pub fn builder() -> GlobalMercatorBuilder {
+        GlobalMercatorBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let mercator = GlobalMercator::builder().build().unwrap();
+        assert_eq!(mercator, GlobalMercator::default());
+        assert_eq!(mercator.max_cached_zoom(), None);
+    }
+
+    #[test]
+    fn test_build() {
+        let mercator = GlobalMercatorBuilder::new()
+            .tile_size(512)
+            .radius(6371000.0)
+            .origin(Origin::TopLeft)
+            .zoom_cache(18)
+            .dpi_scale(2)
+            .build()
+            .unwrap();
+
+        assert_eq!(mercator.tile_size(), 1024);
+        assert_eq!(mercator.pixel_ratio(), 2);
+        assert_eq!(mercator.radius(), 6371000.0);
+        assert_eq!(mercator.origin(), Origin::TopLeft);
+        assert_eq!(mercator.max_cached_zoom(), Some(18));
+
+        let expected = GlobalMercator::with_radius(512, 6371000.0).with_origin(Origin::TopLeft);
+        assert_eq!(mercator.resolution(7), expected.resolution(7));
+        assert_eq!(mercator.tile_bounds(3, 2, 4), expected.tile_bounds(3, 2, 4));
+
+        let f32_mercator = GlobalMercatorBuilder::<f32>::default().tile_size(256).build().unwrap();
+        assert_eq!(f32_mercator, GlobalMercator::<f32>::with_tile_size(256));
+    }
+
+    #[test]
+    fn test_validation() {
+        assert_eq!(GlobalMercator::builder().tile_size(0).build(), Err(GlobalMercatorError::InvalidTileSize(0)));
+        assert_eq!(GlobalMercator::builder().dpi_scale(0).build(), Err(GlobalMercatorError::InvalidDpiScale(0)));
+        assert_eq!(GlobalMercator::builder().radius(0.0).build(), Err(GlobalMercatorError::InvalidRadius(0.0)));
+        assert_eq!(GlobalMercator::builder().radius(-1.0).build(), Err(GlobalMercatorError::InvalidRadius(-1.0)));
+        assert!(GlobalMercator::builder().radius(f64::NAN).build().is_err());
+        assert_eq!(GlobalMercator::builder().radius(f64::INFINITY).build(), Err(GlobalMercatorError::InvalidRadius(f64::INFINITY)));
+        assert!(GlobalMercatorBuilder::<f32>::default().radius(f32::INFINITY).build().is_err());
+    }
+}
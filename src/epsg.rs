@@ -0,0 +1,99 @@
+//! EPSG codes for the projections used by this crate.
+//!
+//! EPSG:900913 ("Google"), EPSG:3857 and "Web Mercator" all name the same spherical Mercator
+//! projection implemented by `GlobalMercator`; 900913 is the original unofficial code, 3857 the
+//! one registered by EPSG. Lat/lon coordinates are WGS84, EPSG:4326.
+
+use crate::{Float, GlobalMercator};
+
+/// Registered code of the spherical Web Mercator projection.
+pub const WEB_MERCATOR: u32 = 3857;
+
+/// Deprecated unofficial code for `WEB_MERCATOR`, still used in the method docs.
+pub const GOOGLE_MERCATOR: u32 = 900913;
+
+/// WGS84 latitude/longitude.
+pub const WGS84: u32 = 4326;
+
+/// Returns `true` for any of the codes naming the Web Mercator projection.
+pub fn is_web_mercator(code: u32) -> bool {
+    code == WEB_MERCATOR || code == GOOGLE_MERCATOR
+}
+
+impl<T: Float> GlobalMercator<T> {
+    /// Alias of `lat_lon_to_meters`, converting WGS84 lat/lon to EPSG:3857.
+    ///
+    /// ```
+    /// use globalmaptiles::GlobalMercator;
+    ///
+    /// let mercator = GlobalMercator::default();
+    /// assert_eq!(mercator.lat_lon_to_epsg3857(48.6, 2.3), mercator.lat_lon_to_meters(48.6, 2.3));
+    /// ```
+    pub fn lat_lon_to_epsg3857(&self, lat: T, lon: T) -> (T, T) {
+        self.lat_lon_to_meters(lat, lon)
+    }
+
+    /// Alias of `meters_to_lat_lon`, converting EPSG:3857 to WGS84 lat/lon.
+    ///
+    /// ```
+    /// use globalmaptiles::GlobalMercator;
+    ///
+    /// let mercator = GlobalMercator::default();
+    /// assert_eq!(mercator.epsg3857_to_lat_lon(256033.0, 6203317.0), mercator.meters_to_lat_lon(256033.0, 6203317.0));
+    /// ```
+    pub fn epsg3857_to_lat_lon(&self, x: T, y: T) -> (T, T) {
+        self.meters_to_lat_lon(x, y)
+    }
+
+    /// Reprojects a WGS84 (EPSG:4326) point to Web Mercator (EPSG:3857) `(x, y)` meters, the
+    /// same as `lat_lon_to_meters`.
+    ///
+    /// ```
+    /// use globalmaptiles::GlobalMercator;
+    ///
+    /// let mercator = GlobalMercator::default();
+    /// let (x, y) = mercator.wgs84_to_web_mercator(51.5074, -0.1278); // London
+    /// assert!((x - -14226.63).abs() < 0.01);
+    /// assert!((y - 6711542.48).abs() < 0.01);
+    ///
+    /// let (lat, lon) = mercator.web_mercator_to_wgs84(x, y);
+    /// assert!((lat - 51.5074).abs() < 1e-9 && (lon - -0.1278).abs() < 1e-9);
+    /// ```
+    pub fn wgs84_to_web_mercator(&self, lat: T, lon: T) -> (T, T) {
+        self.lat_lon_to_meters(lat, lon)
+    }
+
+    /// Reprojects a Web Mercator (EPSG:3857) point back to WGS84 (EPSG:4326) `(lat, lon)`, the
+    /// same as `meters_to_lat_lon`.
+    pub fn web_mercator_to_wgs84(&self, x: T, y: T) -> (T, T) {
+        self.meters_to_lat_lon(x, y)
+    }
+
+    /// Alias of `tile_bounds`, returning the bounds of a TMS tile in EPSG:3857.
+    pub fn tile_bounds_epsg3857(&self, tx: i32, ty: i32, zoom: u32) -> (T, T, T, T) {
+        self.tile_bounds(tx, ty, zoom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_web_mercator() {
+        assert!(is_web_mercator(3857));
+        assert!(is_web_mercator(900913));
+        assert!(!is_web_mercator(WGS84));
+    }
+
+    #[test]
+    fn test_aliases() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.lat_lon_to_epsg3857(-33.9, 151.2), mercator.lat_lon_to_meters(-33.9, 151.2));
+        assert_eq!(mercator.epsg3857_to_lat_lon(1e6, -2e6), mercator.meters_to_lat_lon(1e6, -2e6));
+        assert_eq!(mercator.tile_bounds_epsg3857(134, 90, 8), mercator.tile_bounds(134, 90, 8));
+        assert_eq!(mercator.wgs84_to_web_mercator(-33.9, 151.2), mercator.lat_lon_to_meters(-33.9, 151.2));
+        assert_eq!(mercator.web_mercator_to_wgs84(1e6, -2e6), mercator.meters_to_lat_lon(1e6, -2e6));
+    }
+}
@@ -0,0 +1,113 @@
+use core::fmt;
+use core::ops::Range;
+
+/// Coordinate axis reported by `GlobalMercatorError::OutOfExtent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+/// Error returned by the checked `GlobalMercator` methods.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum GlobalMercatorError {
+    /// Latitude outside `[-90, 90]`.
+    InvalidLatitude(f64),
+    /// Longitude outside `[-180, 180]`.
+    InvalidLongitude(f64),
+    /// Coordinate outside the Mercator extent or the tile grid.
+    OutOfExtent { axis: Axis, value: f64 },
+    /// Quad key containing a character other than `0`-`3`, at the given character index.
+    InvalidQuadKey { position: usize, found: char },
+    /// Quad key without any digit.
+    EmptyQuadKey,
+    /// No zoom level in the searched range is coarse enough for the pixel size.
+    InvalidZoom { pixel_size: f64, searched: Range<u32> },
+    /// Zoom level outside the range supported by the operation.
+    ZoomOutOfRange { zoom: u32, supported: Range<u32> },
+    /// Tile string not made of three `z/x/y` integers.
+    InvalidTilePath,
+    /// Tile size of zero pixels.
+    InvalidTileSize(u32),
+    /// DPI scale of zero.
+    InvalidDpiScale(u32),
+    /// Sphere radius that isn't a positive, finite number of meters.
+    InvalidRadius(f64),
+    /// Tile indices outside the `2^zoom` by `2^zoom` grid.
+    TileOutOfRange { tx: i32, ty: i32, zoom: u32 },
+}
+
+impl fmt::Display for GlobalMercatorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GlobalMercatorError::InvalidLatitude(lat) => write!(f, "latitude {} is outside [-90, 90]", lat),
+            GlobalMercatorError::InvalidLongitude(lon) => write!(f, "longitude {} is outside [-180, 180]", lon),
+            GlobalMercatorError::OutOfExtent { axis, value } => {
+                let axis = match axis {
+                    Axis::X => "x",
+                    Axis::Y => "y",
+                };
+                write!(f, "{} coordinate {} is outside the Mercator extent", axis, value)
+            }
+            GlobalMercatorError::InvalidQuadKey { position, found } => {
+                write!(f, "invalid quad key digit {:?} at position {}", found, position)
+            }
+            GlobalMercatorError::EmptyQuadKey => write!(f, "empty quad key"),
+            GlobalMercatorError::InvalidZoom { pixel_size, searched } => write!(
+                f,
+                "no zoom level in {}..{} matches pixel size {}",
+                searched.start, searched.end, pixel_size
+            ),
+            GlobalMercatorError::ZoomOutOfRange { zoom, supported } => {
+                write!(f, "zoom {} is outside the supported {}..{}", zoom, supported.start, supported.end)
+            }
+            GlobalMercatorError::InvalidTilePath => write!(f, "expected a tile as \"z/x/y\""),
+            GlobalMercatorError::InvalidTileSize(tile_size) => write!(f, "tile size {} is not a positive number of pixels", tile_size),
+            GlobalMercatorError::InvalidDpiScale(scale) => write!(f, "DPI scale {} is not positive", scale),
+            GlobalMercatorError::InvalidRadius(radius) => write!(f, "radius {} is not a positive number of meters", radius),
+            GlobalMercatorError::TileOutOfRange { tx, ty, zoom } => {
+                write!(f, "tile ({}, {}) is outside the grid of zoom {}", tx, ty, zoom)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GlobalMercatorError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(GlobalMercatorError::InvalidLatitude(95.0).to_string(), "latitude 95 is outside [-90, 90]");
+        assert_eq!(GlobalMercatorError::InvalidLongitude(-181.5).to_string(), "longitude -181.5 is outside [-180, 180]");
+        assert_eq!(
+            GlobalMercatorError::OutOfExtent { axis: Axis::Y, value: 2.5e7 }.to_string(),
+            "y coordinate 25000000 is outside the Mercator extent"
+        );
+        assert_eq!(
+            GlobalMercatorError::InvalidQuadKey { position: 2, found: 'x' }.to_string(),
+            "invalid quad key digit 'x' at position 2"
+        );
+        assert_eq!(GlobalMercatorError::EmptyQuadKey.to_string(), "empty quad key");
+        assert_eq!(
+            GlobalMercatorError::InvalidZoom { pixel_size: 0.001, searched: 0..30 }.to_string(),
+            "no zoom level in 0..30 matches pixel size 0.001"
+        );
+        assert_eq!(
+            GlobalMercatorError::ZoomOutOfRange { zoom: 0, supported: 1..24 }.to_string(),
+            "zoom 0 is outside the supported 1..24"
+        );
+        assert_eq!(GlobalMercatorError::InvalidTilePath.to_string(), "expected a tile as \"z/x/y\"");
+        assert_eq!(GlobalMercatorError::InvalidTileSize(0).to_string(), "tile size 0 is not a positive number of pixels");
+        assert_eq!(GlobalMercatorError::InvalidDpiScale(0).to_string(), "DPI scale 0 is not positive");
+        assert_eq!(GlobalMercatorError::InvalidRadius(-1.0).to_string(), "radius -1 is not a positive number of meters");
+        assert_eq!(
+            GlobalMercatorError::TileOutOfRange { tx: 1, ty: 0, zoom: 0 }.to_string(),
+            "tile (1, 0) is outside the grid of zoom 0"
+        );
+    }
+}
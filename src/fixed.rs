@@ -0,0 +1,124 @@
+use crate::GlobalMercator;
+
+/// `GlobalMercator` with the tile size fixed at compile time, e.g. `FixedMercator::<256>::new()`.
+///
+/// Every field derived from `TILE` is a constant, so once inlined the resolution and pixel math
+/// of hot loops folds down to the arithmetic on the coordinates themselves. Only those hot path
+/// conversions are mirrored here; `to_global` gives the equivalent runtime-sized pyramid for
+/// everything else.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FixedMercator<const TILE: u32>;
+
+impl<const TILE: u32> FixedMercator<TILE> {
+    const MERCATOR: GlobalMercator = GlobalMercator::new(TILE);
+
+    pub const fn new() -> FixedMercator<TILE> {
+        FixedMercator
+    }
+
+    /// Returns the runtime-sized pyramid with the same tile size, `GlobalMercator::new(TILE)`.
+    pub const fn to_global(self) -> GlobalMercator {
+        Self::MERCATOR
+    }
+
+    pub fn tile_size(&self) -> u32 {
+        TILE
+    }
+
+    pub fn lat_lon_to_meters(&self, lat: f64, lon: f64) -> (f64, f64) {
+        Self::MERCATOR.lat_lon_to_meters(lat, lon)
+    }
+
+    pub fn meters_to_lat_lon(&self, mx: f64, my: f64) -> (f64, f64) {
+        Self::MERCATOR.meters_to_lat_lon(mx, my)
+    }
+
+    pub fn resolution(&self, zoom: u32) -> f64 {
+        Self::MERCATOR.resolution(zoom)
+    }
+
+    pub fn pixels_to_meters(&self, px: f64, py: f64, zoom: u32) -> (f64, f64) {
+        Self::MERCATOR.pixels_to_meters(px, py, zoom)
+    }
+
+    pub fn meters_to_pixels(&self, mx: f64, my: f64, zoom: u32) -> (f64, f64) {
+        Self::MERCATOR.meters_to_pixels(mx, my, zoom)
+    }
+
+    pub fn pixels_to_tile(&self, px: f64, py: f64) -> (i32, i32) {
+        Self::MERCATOR.pixels_to_tile(px, py)
+    }
+
+    pub fn meters_to_tile(&self, mx: f64, my: f64, zoom: u32) -> (i32, i32) {
+        Self::MERCATOR.meters_to_tile(mx, my, zoom)
+    }
+
+    pub fn lat_lon_to_tile(&self, lat: f64, lon: f64, zoom: u32) -> (i32, i32) {
+        Self::MERCATOR.lat_lon_to_tile(lat, lon, zoom)
+    }
+
+    pub fn tile_bounds(&self, tx: i32, ty: i32, zoom: u32) -> (f64, f64, f64, f64) {
+        Self::MERCATOR.tile_bounds(tx, ty, zoom)
+    }
+
+    pub fn num_tiles(&self, zoom: u32) -> u64 {
+        Self::MERCATOR.num_tiles(zoom)
+    }
+
+    pub fn map_size(&self, zoom: u32) -> u64 {
+        (TILE as u64) << zoom
+    }
+}
+
+impl<const TILE: u32> From<FixedMercator<TILE>> for GlobalMercator {
+    fn from(fixed: FixedMercator<TILE>) -> GlobalMercator {
+        fixed.to_global()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_runtime() {
+        let (fixed, runtime) = (FixedMercator::<512>::new(), GlobalMercator::new(512));
+        assert_eq!(fixed.tile_size(), runtime.tile_size());
+        assert_eq!(GlobalMercator::from(fixed), runtime);
+
+        let (lat, lon, zoom) = (48.6, 2.3, 10);
+        let (mx, my) = fixed.lat_lon_to_meters(lat, lon);
+        assert_eq!((mx, my), runtime.lat_lon_to_meters(lat, lon));
+        assert_eq!(fixed.meters_to_lat_lon(mx, my), runtime.meters_to_lat_lon(mx, my));
+        assert_eq!(fixed.resolution(zoom), runtime.resolution(zoom));
+
+        let (px, py) = fixed.meters_to_pixels(mx, my, zoom);
+        assert_eq!((px, py), runtime.meters_to_pixels(mx, my, zoom));
+        assert_eq!(fixed.pixels_to_meters(px, py, zoom), runtime.pixels_to_meters(px, py, zoom));
+        assert_eq!(fixed.pixels_to_tile(px, py), runtime.pixels_to_tile(px, py));
+        assert_eq!(fixed.meters_to_tile(mx, my, zoom), runtime.meters_to_tile(mx, my, zoom));
+        assert_eq!(fixed.lat_lon_to_tile(lat, lon, zoom), runtime.lat_lon_to_tile(lat, lon, zoom));
+        assert_eq!(fixed.tile_bounds(134, 90, 8), runtime.tile_bounds(134, 90, 8));
+        assert_eq!(fixed.num_tiles(zoom), runtime.num_tiles(zoom));
+        assert_eq!(fixed.map_size(30), runtime.map_size(30));
+    }
+
+    #[test]
+    #[ignore = "timing test, run with --ignored"]
+    fn test_benchmark_against_runtime() {
+        let coords = crate::tests::million_coords();
+        let (fixed, runtime) = (FixedMercator::<256>::new(), GlobalMercator::new(256));
+        let tiles = |to_tile: &dyn Fn(f64, f64) -> (i32, i32)| {
+            let start = std::time::Instant::now();
+            let sum = coords.iter().map(|&(lat, lon)| to_tile(lat, lon)).fold(0i64, |sum, (tx, ty)| sum + tx as i64 + ty as i64);
+            (sum, start.elapsed())
+        };
+
+        let (fixed_sum, fixed_elapsed) = tiles(&|lat, lon| fixed.lat_lon_to_tile(lat, lon, 14));
+        let (runtime_sum, runtime_elapsed) = tiles(&|lat, lon| runtime.lat_lon_to_tile(lat, lon, 14));
+
+        // Timings are too noisy to compare in a test, only check both finish and agree
+        assert_eq!(fixed_sum, runtime_sum);
+        assert!(fixed_elapsed.as_secs() < 10 && runtime_elapsed.as_secs() < 10, "{:?} vs {:?}", fixed_elapsed, runtime_elapsed);
+    }
+}
@@ -0,0 +1,149 @@
+use core::fmt::Debug;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// Floating point type the tile math can run in, implemented for `f32` and `f64`.
+///
+/// The transcendental functions use the standard library when the `std` feature is enabled and
+/// `libm` otherwise.
+pub trait Float:
+    private::Sealed
+    + Copy
+    + Debug
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    const PI: Self;
+    const EPSILON: Self;
+
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(self) -> f64;
+    /// Saturating conversion to `i32`, truncating towards zero like `as`.
+    fn to_i32(self) -> i32;
+    /// Saturating conversion to `i64`, truncating towards zero like `as`.
+    fn to_i64(self) -> i64;
+
+    fn abs(self) -> Self;
+    fn ln(self) -> Self;
+    fn log2(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn asin(self) -> Self;
+    fn atan(self) -> Self;
+    fn exp(self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn ceil(self) -> Self;
+    fn floor(self) -> Self;
+    fn round(self) -> Self;
+}
+
+// A unary function, forwarded to the std method of the same name or to its libm equivalent
+#[cfg(feature = "std")]
+macro_rules! unary {
+    ($t:ident, $name:ident, $libm:ident) => {
+        fn $name(self) -> Self {
+            $t::$name(self)
+        }
+    };
+}
+
+#[cfg(not(feature = "std"))]
+macro_rules! unary {
+    ($t:ident, $name:ident, $libm:ident) => {
+        fn $name(self) -> Self {
+            libm::$libm(self)
+        }
+    };
+}
+
+macro_rules! impl_float {
+    ($t:ident, pow = $pow:ident, { $($name:ident => $libm:ident),* $(,)? }) => {
+        impl Float for $t {
+            const PI: Self = core::$t::consts::PI;
+            const EPSILON: Self = $t::EPSILON;
+
+            fn from_f64(value: f64) -> Self {
+                value as $t
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn to_i32(self) -> i32 {
+                self as i32
+            }
+
+            fn to_i64(self) -> i64 {
+                self as i64
+            }
+
+            #[cfg(feature = "std")]
+            fn powi(self, n: i32) -> Self {
+                $t::powi(self, n)
+            }
+
+            #[cfg(not(feature = "std"))]
+            fn powi(self, n: i32) -> Self {
+                libm::$pow(self, n as $t)
+            }
+
+            #[cfg(feature = "std")]
+            fn powf(self, n: Self) -> Self {
+                $t::powf(self, n)
+            }
+
+            #[cfg(not(feature = "std"))]
+            fn powf(self, n: Self) -> Self {
+                libm::$pow(self, n)
+            }
+
+            $(unary!($t, $name, $libm);)*
+        }
+    };
+}
+
+impl_float!(f32, pow = powf, {
+    abs => fabsf,
+    ln => logf,
+    log2 => log2f,
+    sqrt => sqrtf,
+    sin => sinf,
+    cos => cosf,
+    tan => tanf,
+    asin => asinf,
+    atan => atanf,
+    exp => expf,
+    ceil => ceilf,
+    floor => floorf,
+    round => roundf,
+});
+
+impl_float!(f64, pow = pow, {
+    abs => fabs,
+    ln => log,
+    log2 => log2,
+    sqrt => sqrt,
+    sin => sin,
+    cos => cos,
+    tan => tan,
+    asin => asin,
+    atan => atan,
+    exp => exp,
+    ceil => ceil,
+    floor => floor,
+    round => round,
+});
@@ -0,0 +1,225 @@
+use crate::{Float, GlobalMercator};
+
+/// TMS Global Geodetic profile (EPSG:4326).
+///
+/// Unlike the Mercator pyramid, the unprojected geodetic grid is twice as wide
+/// as it is tall, so zoom 0 is made of two root tiles: one covering longitudes
+/// -180..0 and one covering 0..180, both spanning latitudes -90..90.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GlobalGeodetic {
+    tile_size: u32,
+}
+
+impl Default for GlobalGeodetic {
+    fn default() -> Self {
+        GlobalGeodetic::new(256)
+    }
+}
+
+impl GlobalGeodetic {
+    pub fn new(tile_size: u32) -> GlobalGeodetic {
+        GlobalGeodetic { tile_size }
+    }
+
+    pub fn tile_size(&self) -> u32 {
+        self.tile_size
+    }
+
+    pub fn lat_lon_to_pixels(&self, lat: f64, lon: f64, zoom: u32) -> (f64, f64) {
+        // "Converts lat/lon to pixel coordinates in given zoom of the EPSG:4326 pyramid"
+
+        let res = self.resolution(zoom);
+        let px = (180.0 + lon) / res;
+        let py = (90.0 + lat) / res;
+        (px, py)
+    }
+
+    /// Uses the `ceil - 1` rule of `GlobalMercator::pixels_to_tile`, so a pixel on the edge
+    /// between two tiles belongs to the western or southern one. The result is clamped into the
+    /// grid of `zoom`, so the edges of the world (±180, ±90) map to its first and last tiles.
+    pub fn pixels_to_tile(&self, px: f64, py: f64, zoom: u32) -> (i32, i32) {
+        // "Returns coordinates of the tile covering region in pixel coordinates"
+
+        let (columns, rows) = self.num_tiles(zoom);
+        let tile = |p: f64, n: u64| {
+            let t = Float::ceil(p / self.tile_size as f64) as i64 - 1;
+            t.clamp(0, n.min(i32::MAX as u64) as i64 - 1) as i32
+        };
+        (tile(px, columns), tile(py, rows))
+    }
+
+    pub fn resolution(&self, zoom: u32) -> f64 {
+        // "Resolution (arc/pixel) for given zoom level (measured at Equator)"
+
+        180.0 / self.tile_size as f64 / Float::powi(2.0_f64, zoom as i32)
+    }
+
+    /// Number of tiles along each axis (`(columns, rows)`) at the given zoom.
+    ///
+    /// The geodetic grid has two columns at zoom 0, whereas the Mercator pyramid
+    /// starts from a single square root tile.
+    pub fn num_tiles(&self, zoom: u32) -> (u64, u64) {
+        (2u64 << zoom, 1u64 << zoom)
+    }
+
+    /// Returns which of the two zoom 0 tiles contains the longitude: 0 for the western
+    /// hemisphere and 1 for the eastern one.
+    ///
+    /// The prime meridian belongs to the eastern tile, unlike with `pixels_to_tile` which puts
+    /// points on a tile edge in the western tile. Both ends of the grid stay on their own side: -180
+    /// is in tile 0 and 180 in tile 1.
+    pub fn root_tile_for_lon(&self, lon: f64) -> i32 {
+        if lon < 0.0 {
+            0
+        } else {
+            1
+        }
+    }
+
+    pub fn tile_bounds(&self, tx: i32, ty: i32, zoom: u32) -> (f64, f64, f64, f64) {
+        // "Returns bounds of the given tile" as (min_lat, min_lon, max_lat, max_lon)
+
+        let span = self.tile_size as f64 * self.resolution(zoom);
+        let min_lon = tx as f64 * span - 180.0;
+        let min_lat = ty as f64 * span - 90.0;
+        let max_lon = (tx + 1) as f64 * span - 180.0;
+        let max_lat = (ty + 1) as f64 * span - 90.0;
+        (min_lat, min_lon, max_lat, max_lon)
+    }
+}
+
+impl GlobalGeodetic {
+    /// Returns the Mercator tile containing the center of the given geodetic tile.
+    ///
+    /// Both pyramids have the same zoom numbering, but a geodetic tile usually overlaps several
+    /// Mercator tiles (and sits outside the Mercator extent near the poles), so this is only one
+    /// of the tiles covering it.
+    pub fn to_mercator_tile(&self, tx: i32, ty: i32, zoom: u32, mercator: &GlobalMercator) -> (i32, i32) {
+        let (min_lat, min_lon, max_lat, max_lon) = self.tile_bounds(tx, ty, zoom);
+        mercator.lat_lon_to_tile((min_lat + max_lat) / 2.0, (min_lon + max_lon) / 2.0, zoom)
+    }
+}
+
+impl GlobalMercator {
+    /// Returns the geodetic tile containing the center of the given Mercator tile, with the same
+    /// caveat as `GlobalGeodetic::to_mercator_tile`.
+    pub fn to_geodetic_tile(&self, tx: i32, ty: i32, zoom: u32, geodetic: &GlobalGeodetic) -> (i32, i32) {
+        let (lat, lon) = self.tile_center_lat_lon(tx, ty, zoom);
+        let (px, py) = geodetic.lat_lon_to_pixels(lat, lon, zoom);
+        geodetic.pixels_to_tile(px, py, zoom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(GlobalGeodetic::default().tile_size(), 256);
+    }
+
+    #[test]
+    fn test_resolution() {
+        let geodetic = GlobalGeodetic::default();
+        assert_eq!(geodetic.resolution(0), 180.0 / 256.0);
+        assert_eq!(geodetic.resolution(3), 180.0 / 256.0 / 8.0);
+        assert_eq!(GlobalGeodetic::new(512).resolution(0), 180.0 / 512.0);
+    }
+
+    #[test]
+    fn test_num_tiles() {
+        let geodetic = GlobalGeodetic::default();
+        // Two root tiles side by side, unlike the single Mercator root tile
+        assert_eq!(geodetic.num_tiles(0), (2, 1));
+        assert_eq!(geodetic.num_tiles(3), (16, 8));
+    }
+
+    #[test]
+    fn test_root_tile_bounds() {
+        let geodetic = GlobalGeodetic::default();
+        assert_eq!(geodetic.tile_bounds(0, 0, 0), (-90.0, -180.0, 90.0, 0.0));
+        assert_eq!(geodetic.tile_bounds(1, 0, 0), (-90.0, 0.0, 90.0, 180.0));
+    }
+
+    #[test]
+    fn test_root_tile_for_lon() {
+        let geodetic = GlobalGeodetic::default();
+        assert_eq!(geodetic.root_tile_for_lon(-90.0), 0);
+        assert_eq!(geodetic.root_tile_for_lon(90.0), 1);
+
+        assert_eq!(geodetic.root_tile_for_lon(0.0), 1);
+        assert_eq!(geodetic.root_tile_for_lon(-0.0), 1);
+        assert_eq!(geodetic.root_tile_for_lon(-1e-12), 0);
+        assert_eq!(geodetic.root_tile_for_lon(-180.0), 0);
+        assert_eq!(geodetic.root_tile_for_lon(180.0), 1);
+
+        let (px, py) = geodetic.lat_lon_to_pixels(10.0, 1e-9, 0);
+        assert_eq!(geodetic.pixels_to_tile(px, py, 0).0, geodetic.root_tile_for_lon(1e-9));
+        let (px, py) = geodetic.lat_lon_to_pixels(10.0, 0.0, 0);
+        assert_eq!(geodetic.pixels_to_tile(px, py, 0).0, 0);
+    }
+
+    #[test]
+    fn test_mercator_tiles() {
+        let (geodetic, mercator) = (GlobalGeodetic::default(), GlobalMercator::default());
+        let (tx, ty, zoom) = (33, 23, 5);
+
+        let (min_lat, min_lon, max_lat, max_lon) = geodetic.tile_bounds(tx, ty, zoom);
+        let (mx, my) = mercator.lat_lon_to_meters((min_lat + max_lat) / 2.0, (min_lon + max_lon) / 2.0);
+        let (mtx, mty) = geodetic.to_mercator_tile(tx, ty, zoom, &mercator);
+        let (minx, miny, maxx, maxy) = mercator.tile_bounds(mtx, mty, zoom);
+        assert!(minx <= mx && mx < maxx && miny <= my && my < maxy);
+
+        // The geodetic grid has twice as many columns, so the mapping isn't one to one
+        assert_eq!(geodetic.to_mercator_tile(32, 23, zoom, &mercator).0, mtx);
+        let (gtx, gty) = mercator.to_geodetic_tile(mtx, mty, zoom, &geodetic);
+        let (min_lat, min_lon, max_lat, max_lon) = geodetic.tile_bounds(gtx, gty, zoom);
+        let (lat, lon) = mercator.tile_center_lat_lon(mtx, mty, zoom);
+        // Mercator tile centers fall on the edges between geodetic columns, up to rounding
+        assert!(min_lat <= lat && lat < max_lat, "{} not in [{}, {})", lat, min_lat, max_lat);
+        // and `pixels_to_tile` puts edges in the western column
+        assert!(min_lon < lon && lon <= max_lon + 1e-9, "{} not in ({}, {}]", lon, min_lon, max_lon);
+        assert!(crate::approx_eq_deg(lon, max_lon));
+    }
+
+    #[test]
+    fn test_lat_lon_tile() {
+        let geodetic = GlobalGeodetic::default();
+        let (lat, lon) = (48.6, 2.3);
+        let zoom = 5;
+
+        let (px, py) = geodetic.lat_lon_to_pixels(lat, lon, zoom);
+        let (tx, ty) = geodetic.pixels_to_tile(px, py, zoom);
+        let (min_lat, min_lon, max_lat, max_lon) = geodetic.tile_bounds(tx, ty, zoom);
+
+        assert!(min_lat <= lat && lat < max_lat, "{} not in [{}, {})", lat, min_lat, max_lat);
+        assert!(min_lon <= lon && lon < max_lon, "{} not in [{}, {})", lon, min_lon, max_lon);
+    }
+
+    #[test]
+    fn test_pixels_to_tile_edges() {
+        let geodetic = GlobalGeodetic::default();
+        let tile = |lat: f64, lon: f64, zoom: u32| {
+            let (px, py) = geodetic.lat_lon_to_pixels(lat, lon, zoom);
+            geodetic.pixels_to_tile(px, py, zoom)
+        };
+
+        // The corners of the world stay inside the grid, at every zoom
+        for &zoom in &[0, 1, 5] {
+            let (columns, rows) = geodetic.num_tiles(zoom);
+            let last = (columns as i32 - 1, rows as i32 - 1);
+            assert_eq!(tile(-90.0, -180.0, zoom), (0, 0), "zoom {}", zoom);
+            assert_eq!(tile(90.0, 180.0, zoom), last, "zoom {}", zoom);
+            assert_eq!(tile(90.0, -180.0, zoom), (0, last.1), "zoom {}", zoom);
+            assert_eq!(tile(-90.0, 180.0, zoom), (last.0, 0), "zoom {}", zoom);
+        }
+
+        // Same rule as the Mercator grid on inner edges: the western and southern tile
+        assert_eq!(tile(0.0, 0.0, 1), (1, 0));
+        let mercator = GlobalMercator::default();
+        assert_eq!(mercator.pixels_to_tile(256.0, 256.0), (0, 0));
+        assert_eq!(geodetic.pixels_to_tile(256.0, 256.0, 1), (0, 0));
+    }
+}
@@ -0,0 +1,52 @@
+use alloc::format;
+use alloc::string::String;
+
+use crate::{Float, GlobalMercator};
+
+impl<T: Float> GlobalMercator<T> {
+    /// Returns the outline of the given tile as a GeoJSON `Polygon` Feature.
+    ///
+    /// The ring is closed, counterclockwise and in GeoJSON's lon,lat order. The `x`, `y` and `z`
+    /// properties hold the TMS tile coordinates.
+    pub fn tile_geojson(&self, tx: i32, ty: i32, zoom: u32) -> String {
+        let (min_lat, min_lon, max_lat, max_lon) = self.tile_lat_lon_bounds(tx, ty, zoom);
+        let (min_lat, min_lon, max_lat, max_lon) = (min_lat.to_f64(), min_lon.to_f64(), max_lat.to_f64(), max_lon.to_f64());
+
+        format!(
+            concat!(
+                r#"{{"type":"Feature","#,
+                r#""geometry":{{"type":"Polygon","coordinates":[[[{0},{1}],[{2},{1}],[{2},{3}],[{0},{3}],[{0},{1}]]]}},"#,
+                r#""properties":{{"x":{4},"y":{5},"z":{6}}}}}"#
+            ),
+            min_lon, min_lat, max_lon, max_lat, tx, ty, zoom
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_geojson() {
+        let mercator = GlobalMercator::default();
+        let (tx, ty, zoom) = (134, 90, 8);
+
+        let geojson: serde_json::Value = serde_json::from_str(&mercator.tile_geojson(tx, ty, zoom)).unwrap();
+        assert_eq!(geojson["type"], "Feature");
+        assert_eq!(geojson["geometry"]["type"], "Polygon");
+        assert_eq!(geojson["properties"]["x"], tx);
+        assert_eq!(geojson["properties"]["y"], ty);
+        assert_eq!(geojson["properties"]["z"], zoom);
+
+        let ring = geojson["geometry"]["coordinates"][0].as_array().unwrap();
+        assert_eq!(ring.len(), 5);
+        assert_eq!(ring[0], ring[4]);
+
+        let (min_lat, min_lon, max_lat, max_lon) = mercator.tile_lat_lon_bounds(tx, ty, zoom);
+        assert_eq!(ring[0][0].as_f64(), Some(min_lon));
+        assert_eq!(ring[0][1].as_f64(), Some(min_lat));
+        assert_eq!(ring[2][0].as_f64(), Some(max_lon));
+        assert_eq!(ring[2][1].as_f64(), Some(max_lat));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{GlobalGeodetic, GlobalMercator};
+
+/// Tile grid operations shared by `GlobalMercator` and `GlobalGeodetic`, to write tiling code
+/// generic over the projection.
+///
+/// Tile indices follow each grid's own conventions: a `GlobalMercator` counts rows from its
+/// configured `Origin`, a `GlobalGeodetic` from the south.
+pub trait TileGrid {
+    /// Returns the tile containing the WGS84 point at `zoom`.
+    fn lat_lon_to_tile(&self, lat: f64, lon: f64, zoom: u32) -> (i32, i32);
+
+    /// Returns the bounds of a tile as `(min_lat, min_lon, max_lat, max_lon)`.
+    fn tile_bounds_lat_lon(&self, tx: i32, ty: i32, zoom: u32) -> (f64, f64, f64, f64);
+
+    /// Size of a pixel at `zoom` in the grid's units: meters for Mercator, degrees for geodetic.
+    fn resolution(&self, zoom: u32) -> f64;
+
+    /// Number of tiles along each axis, `(columns, rows)`.
+    fn num_tiles(&self, zoom: u32) -> (u64, u64);
+}
+
+impl TileGrid for GlobalMercator {
+    fn lat_lon_to_tile(&self, lat: f64, lon: f64, zoom: u32) -> (i32, i32) {
+        GlobalMercator::lat_lon_to_tile(self, lat, lon, zoom)
+    }
+
+    fn tile_bounds_lat_lon(&self, tx: i32, ty: i32, zoom: u32) -> (f64, f64, f64, f64) {
+        self.tile_lat_lon_bounds(tx, ty, zoom)
+    }
+
+    fn resolution(&self, zoom: u32) -> f64 {
+        GlobalMercator::resolution(self, zoom)
+    }
+
+    fn num_tiles(&self, zoom: u32) -> (u64, u64) {
+        let n = GlobalMercator::num_tiles(self, zoom);
+        (n, n)
+    }
+}
+
+impl TileGrid for GlobalGeodetic {
+    fn lat_lon_to_tile(&self, lat: f64, lon: f64, zoom: u32) -> (i32, i32) {
+        let (px, py) = self.lat_lon_to_pixels(lat, lon, zoom);
+        self.pixels_to_tile(px, py, zoom)
+    }
+
+    fn tile_bounds_lat_lon(&self, tx: i32, ty: i32, zoom: u32) -> (f64, f64, f64, f64) {
+        self.tile_bounds(tx, ty, zoom)
+    }
+
+    fn resolution(&self, zoom: u32) -> f64 {
+        GlobalGeodetic::resolution(self, zoom)
+    }
+
+    fn num_tiles(&self, zoom: u32) -> (u64, u64) {
+        GlobalGeodetic::num_tiles(self, zoom)
+    }
+}
+
+/// Returns every tile of `grid` at `zoom` covering the given lat/lon rectangle, like
+/// `GlobalMercator::tiles_for_bbox` for any `TileGrid`.
+///
+/// The corners may be given in either order and the tile range is clamped to the grid.
+pub fn tiles_for_bbox<G: TileGrid>(grid: &G, min_lat: f64, min_lon: f64, max_lat: f64, max_lon: f64, zoom: u32) -> impl Iterator<Item = (i32, i32)> {
+    let (x1, y1) = grid.lat_lon_to_tile(min_lat, min_lon, zoom);
+    let (x2, y2) = grid.lat_lon_to_tile(max_lat, max_lon, zoom);
+    let (columns, rows) = grid.num_tiles(zoom);
+    let clamp = |t: i32, n: u64| t.clamp(0, (n - 1).min(i32::MAX as u64) as i32);
+
+    let (tx_min, tx_max) = (clamp(x1.min(x2), columns), clamp(x1.max(x2), columns));
+    let (ty_min, ty_max) = (clamp(y1.min(y2), rows), clamp(y1.max(y2), rows));
+    (tx_min..=tx_max).flat_map(move |tx| (ty_min..=ty_max).map(move |ty| (tx, ty)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Checks that every tile returned overlaps the rectangle, whatever the grid
+    fn assert_covers<G: TileGrid>(grid: &G, min_lat: f64, min_lon: f64, max_lat: f64, max_lon: f64, zoom: u32) -> usize {
+        let mut count = 0;
+        for (tx, ty) in tiles_for_bbox(grid, min_lat, min_lon, max_lat, max_lon, zoom) {
+            let (south, west, north, east) = grid.tile_bounds_lat_lon(tx, ty, zoom);
+            assert!(south <= max_lat && min_lat <= north && west <= max_lon && min_lon <= east, "{}, {} at zoom {}", tx, ty, zoom);
+            count += 1;
+        }
+        count
+    }
+
+    #[test]
+    fn test_generic_tiles_for_bbox() {
+        let (mercator, geodetic) = (GlobalMercator::default(), GlobalGeodetic::default());
+
+        assert_eq!(assert_covers(&mercator, -80.0, -179.0, 80.0, 179.0, 0), 1);
+        assert_eq!(assert_covers(&geodetic, -80.0, -179.0, 80.0, 179.0, 0), 2);
+        assert_eq!(assert_covers(&geodetic, -90.0, -180.0, 90.0, 180.0, 0), 2);
+
+        let (min_lat, min_lon, max_lat, max_lon) = (40.0, -10.0, 60.0, 30.0);
+        let count = assert_covers(&mercator, min_lat, min_lon, max_lat, max_lon, 5);
+        assert_eq!(count, mercator.tiles_for_bbox(min_lat, min_lon, max_lat, max_lon, 5).count());
+        assert_eq!(assert_covers(&geodetic, min_lat, min_lon, max_lat, max_lon, 5), 8 * 4);
+    }
+
+    #[test]
+    fn test_grid_metadata() {
+        let (mercator, geodetic) = (GlobalMercator::default(), GlobalGeodetic::default());
+
+        assert_eq!(TileGrid::num_tiles(&mercator, 3), (8, 8));
+        assert_eq!(TileGrid::num_tiles(&geodetic, 3), (16, 8));
+        assert_eq!(TileGrid::resolution(&mercator, 3), mercator.resolution(3));
+        assert_eq!(TileGrid::resolution(&geodetic, 3), geodetic.resolution(3));
+        assert_eq!(TileGrid::lat_lon_to_tile(&mercator, 48.6, 2.3, 8), mercator.lat_lon_to_tile(48.6, 2.3, 8));
+    }
+}
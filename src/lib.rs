@@ -1,10 +1,181 @@
-use std::f64::consts::PI;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+use core::f64::consts::PI;
+
+mod approx;
+mod bounds;
+mod builder;
+pub mod epsg;
+mod error;
+mod fixed;
+mod float;
+mod geodetic;
+#[cfg(feature = "alloc")]
+mod geojson;
+mod grid;
+#[cfg(feature = "alloc")]
+mod manifest;
+mod navigation;
+#[cfg(feature = "alloc")]
+mod path;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod tile;
+mod tilejson;
+mod units;
+#[cfg(feature = "alloc")]
+mod url;
+#[cfg(feature = "alloc")]
+mod wkt;
+#[cfg(feature = "alloc")]
+mod wmts;
+mod zoom_level;
+
+pub use approx::{approx_eq_deg, approx_eq_meters, DEGREES_TOLERANCE, METERS_TOLERANCE};
+pub use bounds::Bounds;
+pub use builder::GlobalMercatorBuilder;
+pub use error::{Axis, GlobalMercatorError};
+pub use fixed::FixedMercator;
+pub use float::Float;
+pub use geodetic::GlobalGeodetic;
+pub use grid::{tiles_for_bbox, TileGrid};
+#[cfg(feature = "alloc")]
+pub use manifest::TileEntry;
+pub use navigation::{Adjacency, Direction};
+pub use tile::Tile;
+pub use tilejson::TileJsonExtent;
+pub use units::{LatLon, Meters, Pixels, TileXy};
+#[cfg(feature = "alloc")]
+pub use url::TileScheme;
+#[cfg(feature = "alloc")]
+pub use wkt::WktCrs;
+#[cfg(feature = "alloc")]
+pub use wmts::TileMatrix;
+pub use zoom_level::ZoomLevel;
+
+/// Semi-major axis of the WGS84 ellipsoid in meters, used as the sphere radius by default.
+pub const WGS84_RADIUS: f64 = 6378137.0;
+
+/// Latitude cutoff of the Web Mercator square, in degrees.
+///
+/// `lat_lon_to_meters` reaches `my == origin_shift` (the top edge of the square extent) at this
+/// latitude, solving `ln(tan(PI / 4 + lat / 2)) == PI`, i.e. `lat == 2 * atan(e^PI) - PI / 2`.
+/// Anything further north or south falls outside the tile pyramid.
+pub const MAX_LATITUDE: f64 = 85.05112878;
+
+/// OGC standardized rendering pixel size (0.28mm) used to derive WMTS scale denominators.
+pub const WMTS_PIXEL_SIZE: f64 = 0.00028;
+
+/// Resolutions in meters/pixel of zooms 0 to 22 of the standard 256 pixel pyramid, as
+/// returned by `GlobalMercator::new(256).resolution(zoom)`.
+pub const STANDARD_RESOLUTIONS: [f64; 23] = [
+    156543.03392804097,
+    78271.51696402048,
+    39135.75848201024,
+    19567.87924100512,
+    9783.93962050256,
+    4891.96981025128,
+    2445.98490512564,
+    1222.99245256282,
+    611.49622628141,
+    305.748113140705,
+    152.8740565703525,
+    76.43702828517625,
+    38.21851414258813,
+    19.109257071294063,
+    9.554628535647032,
+    4.777314267823516,
+    2.388657133911758,
+    1.194328566955879,
+    0.5971642834779395,
+    0.29858214173896974,
+    0.14929107086948487,
+    0.07464553543474244,
+    0.03732276771737122,
+];
+
+/// Scale denominators of zooms 0 to 22 of the GoogleMapsCompatible WMTS matrix set, as returned
+/// by `GlobalMercator::new(256).scale_denominator(zoom)`.
+pub const STANDARD_SCALE_DENOMINATORS: [f64; 23] = [
+    559082264.0287178,
+    279541132.0143589,
+    139770566.00717944,
+    69885283.00358972,
+    34942641.50179486,
+    17471320.75089743,
+    8735660.375448715,
+    4367830.1877243575,
+    2183915.0938621787,
+    1091957.5469310894,
+    545978.7734655447,
+    272989.38673277234,
+    136494.69336638617,
+    68247.34668319309,
+    34123.67334159654,
+    17061.83667079827,
+    8530.918335399136,
+    4265.459167699568,
+    2132.729583849784,
+    1066.364791924892,
+    533.182395962446,
+    266.591197981223,
+    133.2955989906115,
+];
+
+/// Number of zoom levels `GlobalMercator::with_zoom_cache` can precompute (zooms `0..=31`).
+pub const ZOOM_CACHE_LEN: usize = 32;
+
+/// Corner of the extent tile rows are counted from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Origin {
+    /// TMS rows, counted northwards from the bottom-left corner.
+    #[default]
+    BottomLeft,
+    /// Google/XYZ rows, counted southwards from the top-left corner.
+    TopLeft,
+}
+
+/// Coordinate system of the bounds returned by `GlobalMercator::tile_bounds_in`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Crs {
+    /// EPSG:900913 easting/northing in meters.
+    Meters,
+    /// WGS84 longitude/latitude in degrees.
+    Degrees,
+}
+
+/// Which tile corner `GlobalMercator::snap_to_tile_grid` moves a point to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CornerSnap {
+    /// North-west corner of the tile containing the point.
+    NorthWest,
+    /// Closest of the four corners of the tile containing the point, in Mercator meters.
+    Nearest,
+}
+
+/// TMS Global Mercator pyramid (EPSG:900913).
+///
+/// The math runs in `T`, which defaults to `f64`. `GlobalMercator<f32>` trades precision for
+/// size, matching what GPU shaders use; build one with `with_tile_size` or `with_radius`.
+///
+/// Tile indices are `i32`, which holds every tile of zooms up to 31. Google rows are `i64`, and
+/// `pixels_to_tile_i64` covers deeper zooms.
 #[derive(Debug, Clone, Copy)]
-pub struct GlobalMercator {
+pub struct GlobalMercator<T = f64> {
     tile_size: u32,
-    initial_resolution: f64,
-    origin_shift: f64,
+    radius: T,
+    initial_resolution: T,
+    origin_shift: T,
+    cached_zooms: u32,
+    resolutions: [T; ZOOM_CACHE_LEN],
+    origin: Origin,
+    pixel_ratio: u32,
 }
 
 impl Default for GlobalMercator {
@@ -15,206 +186,2692 @@ impl Default for GlobalMercator {
 
 impl GlobalMercator {
     // Initialize the TMS Global Mercator pyramid
-    pub fn new(tile_size: u32) -> GlobalMercator {
+    //
+    // Usable in constants, e.g. `const MERCATOR: GlobalMercator = GlobalMercator::new(256);`.
+    // Trait methods can't be called in a const fn, so this spells out `with_radius` for f64.
+    pub const fn new(tile_size: u32) -> GlobalMercator {
         GlobalMercator {
             tile_size,
-            initial_resolution: 2.0 * PI * 6378137.0 / tile_size as f64,
+            radius: WGS84_RADIUS,
+            initial_resolution: 2.0 * PI * WGS84_RADIUS / tile_size as f64,
+            origin_shift: 2.0 * PI * WGS84_RADIUS / 2.0,
+            cached_zooms: 0,
+            resolutions: [0.0; ZOOM_CACHE_LEN],
+            origin: Origin::BottomLeft,
+            pixel_ratio: 1,
+        }
+    }
+}
+
+fn clamp_latitude<T: Float>(lat: T) -> T {
+    let max = T::from_f64(MAX_LATITUDE);
+    if lat > max {
+        max
+    } else if lat < -max {
+        -max
+    } else {
+        lat
+    }
+}
+
+impl<T: Float> GlobalMercator<T> {
+    /// Same as `new` for any float type, e.g. `GlobalMercator::<f32>::with_tile_size(256)`.
+    pub fn with_tile_size(tile_size: u32) -> GlobalMercator<T> {
+        GlobalMercator::with_radius(tile_size, T::from_f64(WGS84_RADIUS))
+    }
+
+    /// Initialize the pyramid on a sphere of the given radius in meters instead of the WGS84
+    /// semi-major axis.
+    pub fn with_radius(tile_size: u32, radius: T) -> GlobalMercator<T> {
+        let two = T::from_f64(2.0);
+        GlobalMercator {
+            tile_size,
+            radius,
+            initial_resolution: two * T::PI * radius / T::from_f64(tile_size as f64),
             // 156543.03392804062 for tile_size 256 pixels
-            origin_shift: 2.0 * PI * 6378137.0 / 2.0,
+            origin_shift: two * T::PI * radius / two,
             // 20037508.342789244
+            cached_zooms: 0,
+            resolutions: [T::from_f64(0.0); ZOOM_CACHE_LEN],
+            origin: Origin::BottomLeft,
+            pixel_ratio: 1,
+        }
+    }
+
+    /// Same as `with_tile_size`, with the resolutions of zooms `0..=max_zoom` precomputed so
+    /// `resolution` becomes a table lookup for hot loops.
+    ///
+    /// The table is stored inline so the struct stays `Copy`, at the cost of every
+    /// `GlobalMercator` carrying `ZOOM_CACHE_LEN` floats whether the cache is used or not.
+    /// `max_zoom` is capped at `ZOOM_CACHE_LEN - 1`, higher zooms are computed on demand.
+    pub fn with_zoom_cache(tile_size: u32, max_zoom: u32) -> GlobalMercator<T> {
+        GlobalMercator::with_tile_size(tile_size).zoom_cache(max_zoom)
+    }
+
+    /// Initialize a pyramid of high-DPI tiles, e.g. `with_dpi_scale(256, 2)` for @2x tiles.
+    ///
+    /// `new(512)` halves the resolution: zoom `z` has as many tiles as `new(256)`, each covering
+    /// as much ground as a 256 pixel tile at `z + 1`. A DPI-scaled pyramid instead keeps the
+    /// grid and resolutions of `base_tile_size` tiles and only renders them with `scale` times
+    /// more pixels: `tile_size` reports `base_tile_size * scale`, while every pixel coordinate
+    /// and resolution is expressed in `base_tile_size` (logical) pixels.
+    pub fn with_dpi_scale(base_tile_size: u32, scale: u32) -> GlobalMercator<T> {
+        let mut mercator = GlobalMercator::with_tile_size(base_tile_size);
+        mercator.pixel_ratio = scale;
+        mercator
+    }
+
+    fn zoom_cache(mut self, max_zoom: u32) -> GlobalMercator<T> {
+        self.cached_zooms = max_zoom.min(ZOOM_CACHE_LEN as u32 - 1) + 1;
+        for zoom in 0..self.cached_zooms {
+            self.resolutions[zoom as usize] = self.compute_resolution(zoom);
+        }
+        self
+    }
+
+    /// Returns the pyramid with tile rows counted from the given corner.
+    ///
+    /// This applies to the tiles returned by `meters_to_tile` (and its variants,
+    /// `lat_lon_to_tile` and `tiles_for_bbox`) and taken by `tile_bounds` (and the other
+    /// bounds and center methods). Methods named after a convention, such as `google_tile` or
+    /// `quad_tree`, keep working on TMS coordinates.
+    pub fn with_origin(mut self, origin: Origin) -> GlobalMercator<T> {
+        self.origin = origin;
+        self
+    }
+
+    pub fn origin(&self) -> Origin {
+        self.origin
+    }
+
+    // Converts a row between TMS and the configured origin, both ways
+    fn origin_row(&self, ty: i32, zoom: u32) -> i32 {
+        match self.origin {
+            Origin::BottomLeft => ty,
+            Origin::TopLeft => self.flip_tile_y(ty, zoom),
         }
     }
 
+    /// Highest zoom with a precomputed resolution, if the cache is enabled.
+    pub fn max_cached_zoom(&self) -> Option<u32> {
+        self.cached_zooms.checked_sub(1)
+    }
+
+    /// Size of the tile images in pixels, including the DPI scale of `with_dpi_scale`.
     pub fn tile_size(&self) -> u32 {
-        self.tile_size
+        self.tile_size * self.pixel_ratio
+    }
+
+    /// Number of image pixels per logical pixel, 1 unless built `with_dpi_scale`.
+    pub fn pixel_ratio(&self) -> u32 {
+        self.pixel_ratio
+    }
+
+    pub fn radius(&self) -> T {
+        self.radius
     }
 
-    pub fn lat_lon_to_meters(&self, lat: f64, lon: f64) -> (f64, f64) {
+    /// Assumes `lat` and `lon` are valid WGS84 coordinates, out of range latitudes produce
+    /// `NaN` or infinite meters. Use `try_lat_lon_to_meters` to validate the input.
+    pub fn lat_lon_to_meters(&self, lat: T, lon: T) -> (T, T) {
         // "Converts given lat/lon in WGS84 Datum to XY in Spherical Mercator EPSG:900913"
 
-        let mx = lon * self.origin_shift / 180.0;
-        let my = f64::ln(f64::tan((90.0 + lat) * PI / 360.0)) / (PI / 180.0);
+        let (d90, d180, d360) = (T::from_f64(90.0), T::from_f64(180.0), T::from_f64(360.0));
+        let mx = lon * self.origin_shift / d180;
+        let my = ((d90 + lat) * T::PI / d360).tan().ln() / (T::PI / d180);
+
+        let my = my * self.origin_shift / d180;
+        (mx, my)
+    }
+
+    /// Checked version of `lat_lon_to_meters` rejecting `lat` outside `[-90, 90]` and `lon`
+    /// outside `[-180, 180]`.
+    ///
+    /// The poles themselves are accepted but project far outside the square Mercator extent.
+    pub fn try_lat_lon_to_meters(&self, lat: T, lon: T) -> Result<(T, T), GlobalMercatorError> {
+        let (d90, d180) = (T::from_f64(90.0), T::from_f64(180.0));
+        if !(-d90..=d90).contains(&lat) {
+            return Err(GlobalMercatorError::InvalidLatitude(lat.to_f64()));
+        }
+        if !(-d180..=d180).contains(&lon) {
+            return Err(GlobalMercatorError::InvalidLongitude(lon.to_f64()));
+        }
+        Ok(self.lat_lon_to_meters(lat, lon))
+    }
+
+    /// Same as `lat_lon_to_meters`, with the latitude clamped to `±MAX_LATITUDE` first so the
+    /// result always lies inside the square Mercator extent.
+    pub fn lat_lon_to_meters_clamped(&self, lat: T, lon: T) -> (T, T) {
+        self.lat_lon_to_meters(clamp_latitude(lat), lon)
+    }
 
-        let my = my * self.origin_shift / 180.0;
-        return (mx, my);
+    /// Returns `true` if the point is on the square Mercator map: `lon` in `[-180, 180]` and
+    /// `lat` in `[-MAX_LATITUDE, MAX_LATITUDE]`, both inclusive.
+    pub fn is_in_mercator_extent(&self, lat: T, lon: T) -> bool {
+        let (max_lat, d180) = (T::from_f64(MAX_LATITUDE), T::from_f64(180.0));
+        (-max_lat..=max_lat).contains(&lat) && (-d180..=d180).contains(&lon)
     }
 
-    pub fn meters_to_lat_lon(&self, mx: f64, my: f64) -> (f64, f64) {
+    pub fn meters_to_lat_lon(&self, mx: T, my: T) -> (T, T) {
         // "Converts XY point from Spherical Mercator EPSG:900913 to lat/lon in WGS84 Datum"
 
-        let lon = (mx / self.origin_shift) * 180.0;
-        let lat = (my / self.origin_shift) * 180.0;
+        let (two, d180) = (T::from_f64(2.0), T::from_f64(180.0));
+        let lon = (mx / self.origin_shift) * d180;
+        let lat = (my / self.origin_shift) * d180;
+
+        let lat = d180 / T::PI * (two * (lat * T::PI / d180).exp().atan() - T::PI / two);
+        (lat, lon)
+    }
+
+    /// Runs `lat_lon_to_meters` over a slice of `(lat, lon)` pairs, writing into `out`.
+    ///
+    /// `out` must be the same length as `coords`.
+    pub fn lat_lon_to_meters_batch(&self, coords: &[(T, T)], out: &mut [(T, T)]) {
+        debug_assert_eq!(coords.len(), out.len());
+        for (&(lat, lon), meters) in coords.iter().zip(out.iter_mut()) {
+            *meters = self.lat_lon_to_meters(lat, lon);
+        }
+    }
 
-        let lat = 180.0 / PI * (2.0 * f64::atan(f64::exp(lat * PI / 180.0)) - PI / 2.0);
-        return (lat, lon);
+    /// Runs `meters_to_lat_lon` over a slice of `(mx, my)` pairs, writing into `out`.
+    ///
+    /// `out` must be the same length as `coords`.
+    pub fn meters_to_lat_lon_batch(&self, coords: &[(T, T)], out: &mut [(T, T)]) {
+        debug_assert_eq!(coords.len(), out.len());
+        for (&(mx, my), lat_lon) in coords.iter().zip(out.iter_mut()) {
+            *lat_lon = self.meters_to_lat_lon(mx, my);
+        }
     }
 
-    pub fn pixels_to_meters(&self, px: f64, py: f64, zoom: u32) -> (f64, f64) {
+    pub fn pixels_to_meters(&self, px: T, py: T, zoom: u32) -> (T, T) {
         // "Converts pixel coordinates in given zoom level of pyramid to EPSG:900913"
 
         let res = self.resolution(zoom);
         let mx = px * res - self.origin_shift;
         let my = py * res - self.origin_shift;
-        return (mx, my);
+        (mx, my)
     }
 
-    pub fn meters_to_pixels(&self, mx: f64, my: f64, zoom: u32) -> (f64, f64) {
+    pub fn meters_to_pixels(&self, mx: T, my: T, zoom: u32) -> (T, T) {
         // "Converts EPSG:900913 to pyramid pixel coordinates in given zoom level"
 
         let res = self.resolution(zoom);
         let px = (mx + self.origin_shift) / res;
         let py = (my + self.origin_shift) / res;
-        return (px, py);
+        (px, py)
+    }
+
+    /// Same as `pixels_to_meters` at a fractional zoom, for continuous zooming.
+    pub fn pixels_to_meters_f(&self, px: T, py: T, zoom: T) -> (T, T) {
+        let res = self.fractional_resolution(zoom);
+        (px * res - self.origin_shift, py * res - self.origin_shift)
+    }
+
+    /// Same as `meters_to_pixels` at a fractional zoom, for continuous zooming.
+    pub fn meters_to_pixels_f(&self, mx: T, my: T, zoom: T) -> (T, T) {
+        let res = self.fractional_resolution(zoom);
+        ((mx + self.origin_shift) / res, (my + self.origin_shift) / res)
+    }
+
+    /// Converts pyramid pixel coordinates in given zoom level to lat/lon.
+    ///
+    /// Pixels use the same bottom-left origin as `pixels_to_meters`, use `pixels_to_raster` for
+    /// top-left image coordinates.
+    pub fn pixels_to_lat_lon(&self, px: T, py: T, zoom: u32) -> (T, T) {
+        let (mx, my) = self.pixels_to_meters(px, py, zoom);
+        self.meters_to_lat_lon(mx, my)
+    }
+
+    /// Converts lat/lon to pyramid pixel coordinates in given zoom level, with the bottom-left
+    /// origin of `meters_to_pixels`.
+    pub fn lat_lon_to_pixels(&self, lat: T, lon: T, zoom: u32) -> (T, T) {
+        let (mx, my) = self.lat_lon_to_meters(lat, lon);
+        self.meters_to_pixels(mx, my, zoom)
     }
 
-    pub fn pixels_to_tile(&self, px: f64, py: f64) -> (i32, i32) {
+    pub fn pixels_to_tile(&self, px: T, py: T) -> (i32, i32) {
         // "Returns a tile covering region in given pixel coordinates"
 
-        let tx = f64::ceil(px / self.tile_size as f64) as i32 - 1;
-        let ty = f64::ceil(py / self.tile_size as f64) as i32 - 1;
-        return (tx, ty);
+        let (one, tile_size) = (T::from_f64(1.0), T::from_f64(self.tile_size as f64));
+        let tx = ((px / tile_size).ceil() - one).to_i32();
+        let ty = ((py / tile_size).ceil() - one).to_i32();
+        (tx, ty)
+    }
+
+    /// Same as `pixels_to_tile`, with pixels within a rounding error of an integer snapped onto
+    /// it first.
+    ///
+    /// Tile edges converted from meters often land a hair off the integer pixel, e.g. at
+    /// `34303.999999` or `34304.000001`, which puts them on either side of the edge. Snapping
+    /// sends them all to the tile `pixels_to_tile` picks for the exact edge, the one before it.
+    /// The tolerance is 1e-6 pixels plus a few ulps of the coordinate, for deep zooms.
+    pub fn pixels_to_tile_exact(&self, px: T, py: T) -> (i32, i32) {
+        let snap = |p: T| {
+            let rounded = p.round();
+            let epsilon = T::from_f64(1e-6) + p.abs() * T::EPSILON * T::from_f64(4.0);
+            if (p - rounded).abs() <= epsilon {
+                rounded
+            } else {
+                p
+            }
+        };
+        self.pixels_to_tile(snap(px), snap(py))
+    }
+
+    /// Same as `pixels_to_tile` with `i64` indices.
+    ///
+    /// The `i32` tile indices used by the rest of the API cover every tile up to zoom 31;
+    /// beyond that `pixels_to_tile` saturates at `i32::MAX` while this keeps counting.
+    pub fn pixels_to_tile_i64(&self, px: T, py: T) -> (i64, i64) {
+        let (one, tile_size) = (T::from_f64(1.0), T::from_f64(self.tile_size as f64));
+        let tx = ((px / tile_size).ceil() - one).to_i64();
+        let ty = ((py / tile_size).ceil() - one).to_i64();
+        (tx, ty)
     }
 
-    pub fn pixels_to_raster(&self, px: f64, py: f64, zoom: u32) -> (f64, f64) {
+    pub fn pixels_to_raster(&self, px: T, py: T, zoom: u32) -> (T, T) {
         // "Move the origin of pixel coordinates to top-left corner"
 
-        let map_size = self.tile_size << zoom;
-        return (px, map_size as f64 - py);
+        (px, T::from_f64(self.map_size(zoom) as f64) - py)
+    }
+
+    /// Returns the Google/XYZ tile, with rows counted from the top-left corner, containing the
+    /// given TMS pixel. The pixel is flipped to the raster origin with `pixels_to_raster` before
+    /// `pixels_to_tile`.
+    ///
+    /// This matches `google_tile(pixels_to_tile(px, py))` except for pixels exactly on the edge
+    /// between two rows: these land in the northern row here and in the southern one there. For
+    /// integer pixels of a raster image see `raster_pixels_to_tile`.
+    pub fn pixels_to_raster_tile(&self, px: T, py: T, zoom: u32) -> (i32, i32) {
+        let (px, py) = self.pixels_to_raster(px, py, zoom);
+        self.pixels_to_tile(px, py)
+    }
+
+    /// Returns the pixel offset of the top-left corner of a TMS tile in a full map image with
+    /// its origin at the top-left corner, as used when writing tiled rasters.
+    pub fn tile_to_raster_pixels(&self, tx: i32, ty: i32, zoom: u32) -> (i64, i64) {
+        let tile_size = self.tile_size as i64;
+        (tx as i64 * tile_size, self.flip_row(ty as i64, zoom) * tile_size)
+    }
+
+    /// Returns the TMS tile containing a pixel of a full map image with its origin at the
+    /// top-left corner, the inverse of `tile_to_raster_pixels`.
+    pub fn raster_pixels_to_tile(&self, px: i64, py: i64, zoom: u32) -> (i32, i32) {
+        let tile_size = self.tile_size as i64;
+        (px.div_euclid(tile_size) as i32, self.flip_row(py.div_euclid(tile_size), zoom) as i32)
     }
 
-    pub fn meters_to_tile(&self, mx: f64, my: f64, zoom: u32) -> (i32, i32) {
+    pub fn meters_to_tile(&self, mx: T, my: T, zoom: u32) -> (i32, i32) {
         // "Returns tile for given mercator coordinates"
 
         let (px, py) = self.meters_to_pixels(mx, my, zoom);
-        return self.pixels_to_tile(px, py);
+        let (tx, ty) = self.pixels_to_tile(px, py);
+        (tx, self.origin_row(ty, zoom))
+    }
+
+    /// Same as `meters_to_tile`, with the column wrapped modulo `2^zoom` so coordinates past the
+    /// antimeridian (e.g. a track continuing east of 180°) land back on the grid.
+    ///
+    /// The row is not wrapped since there is nothing past the poles.
+    pub fn meters_to_tile_wrapped(&self, mx: T, my: T, zoom: u32) -> (i32, i32) {
+        let (tx, ty) = self.meters_to_tile(mx, my, zoom);
+        ((tx as i64).rem_euclid(self.grid_len(zoom)) as i32, ty)
+    }
+
+    /// Same as `meters_to_tile`, except that coordinates within `epsilon` meters of the
+    /// meridian or the equator are snapped onto the east and north side respectively.
+    ///
+    /// `lat_lon_to_meters(0, 0)` lands exactly on (or a rounding error below) both axes, which
+    /// `meters_to_tile` resolves to the tile south-west of the origin. Snapping picks the tile
+    /// north-east of it instead, e.g. quad key `122222222222` at zoom 12.
+    pub fn meters_to_tile_snapped(&self, mx: T, my: T, zoom: u32, epsilon: T) -> (i32, i32) {
+        let (tx, ty) = self.meters_to_tile(mx, my, zoom);
+        let positive_side = (self.num_tiles(zoom) / 2) as i32;
+        let snap = |m: T, t: i32, side: i32| if m.abs() <= epsilon { side } else { t };
+        (snap(mx, tx, positive_side), snap(my, ty, self.origin_row(positive_side, zoom)))
+    }
+
+    /// Checked version of `meters_to_tile` rejecting coordinates outside the square Mercator
+    /// extent `[-origin_shift, origin_shift]` or landing on a tile outside `0..2^zoom`.
+    pub fn try_meters_to_tile(&self, mx: T, my: T, zoom: u32) -> Result<(i32, i32), GlobalMercatorError> {
+        let (tx, ty) = self.meters_to_tile(mx, my, zoom);
+        let n = self.grid_len(zoom);
+        let in_range = |m: T, t: i32| -self.origin_shift <= m && m <= self.origin_shift && 0 <= t && (t as i64) < n;
+
+        if !in_range(mx, tx) {
+            return Err(GlobalMercatorError::OutOfExtent { axis: Axis::X, value: mx.to_f64() });
+        }
+        if !in_range(my, ty) {
+            return Err(GlobalMercatorError::OutOfExtent { axis: Axis::Y, value: my.to_f64() });
+        }
+        Ok((tx, ty))
+    }
+
+    /// Returns the tile covering the given lat/lon.
+    pub fn lat_lon_to_tile(&self, lat: T, lon: T, zoom: u32) -> (i32, i32) {
+        let (mx, my) = self.lat_lon_to_meters(lat, lon);
+        self.meters_to_tile(mx, my, zoom)
+    }
+
+    /// Returns the tile covering the given lat/lon and the position of the point inside it, in
+    /// pixels from the bottom-left corner of the tile like pyramid pixels.
+    ///
+    /// The offset is the pixel position minus the origin of the tile `pixels_to_tile` picks, so
+    /// it lies in `(0, tile_size]`: a point on a tile edge belongs to the tile below or to the
+    /// left of it and is at `tile_size` in that tile, never at 0 in the next one.
+    pub fn lat_lon_to_tile_pixel(&self, lat: T, lon: T, zoom: u32) -> ((i32, i32), (T, T)) {
+        let (px, py) = self.lat_lon_to_pixels(lat, lon, zoom);
+        let (tx, ty) = self.pixels_to_tile(px, py);
+
+        let tile_size = self.tile_size as f64;
+        let local_x = px - T::from_f64(tx as f64 * tile_size);
+        let local_y = py - T::from_f64(ty as f64 * tile_size);
+        ((tx, self.origin_row(ty, zoom)), (local_x, local_y))
+    }
+
+    /// Returns the Google/XYZ tile covering the given lat/lon.
+    pub fn lat_lon_to_google_tile(&self, lat: T, lon: T, zoom: u32) -> (i64, i64) {
+        let (tx, ty) = self.lat_lon_to_tile(lat, lon, zoom);
+        self.google_tile(tx, self.origin_row(ty, zoom), zoom)
     }
 
-    pub fn tile_bounds(&self, tx: i32, ty: i32, zoom: u32) -> (f64, f64, f64, f64) {
+    /// Returns every tile at `zoom` covering the given lat/lon rectangle.
+    ///
+    /// The corners may be given in either order and the tile range is clamped to the grid.
+    pub fn tiles_for_bbox(&self, min_lat: T, min_lon: T, max_lat: T, max_lon: T, zoom: u32) -> impl Iterator<Item = (i32, i32)> {
+        let (tx_min, ty_min, tx_max, ty_max) = self.bbox_tile_range(min_lat, min_lon, max_lat, max_lon, zoom);
+        (tx_min..=tx_max).flat_map(move |tx| (ty_min..=ty_max).map(move |ty| (tx, ty)))
+    }
+
+    /// Splits a lat/lon rectangle crossing the antimeridian, i.e. with `min_lon > max_lon`, into
+    /// the part east of `min_lon` up to 180 and the part from -180 west of `max_lon`.
+    ///
+    /// Other rectangles are returned as is. Use this before `tiles_for_bbox`, which otherwise
+    /// takes the corners in either order and covers the rest of the world instead.
+    #[cfg(feature = "alloc")]
+    pub fn split_antimeridian(&self, min_lat: T, min_lon: T, max_lat: T, max_lon: T) -> Vec<(T, T, T, T)> {
+        if min_lon <= max_lon {
+            return alloc::vec![(min_lat, min_lon, max_lat, max_lon)];
+        }
+        let d180 = T::from_f64(180.0);
+        alloc::vec![(min_lat, min_lon, max_lat, d180), (min_lat, -d180, max_lat, max_lon)]
+    }
+
+    /// Returns every tile covering the given lat/lon rectangle from `min_zoom` to `max_zoom`
+    /// inclusive, as `(tx, ty, zoom)`, zoom by zoom.
+    ///
+    /// The tiles are produced lazily, so deep pyramids can be consumed incrementally.
+    pub fn pyramid_tiles(&self, min_lat: T, min_lon: T, max_lat: T, max_lon: T, min_zoom: u32, max_zoom: u32) -> impl Iterator<Item = (i32, i32, u32)> {
+        let mercator = *self;
+        (min_zoom..=max_zoom).flat_map(move |zoom| mercator.tiles_for_bbox(min_lat, min_lon, max_lat, max_lon, zoom).map(move |(tx, ty)| (tx, ty, zoom)))
+    }
+
+    /// Splits the tiles `tiles_for_bbox` covers into rectangles of `chunk_tiles` by `chunk_tiles`
+    /// tiles, as inclusive `(tx_min, ty_min, tx_max, ty_max)` ranges, e.g. to process them in
+    /// parallel.
+    ///
+    /// Chunks start at the minimum corner of the range, so the last column and row of chunks
+    /// may be narrower. They are ordered like `tiles_for_bbox`, column by column.
+    #[cfg(feature = "alloc")]
+    pub fn tile_aligned_chunks(&self, min_lat: T, min_lon: T, max_lat: T, max_lon: T, zoom: u32, chunk_tiles: u32) -> Vec<(i32, i32, i32, i32)> {
+        assert!(chunk_tiles > 0, "chunks must be at least one tile wide");
+        let (tx_min, ty_min, tx_max, ty_max) = self.bbox_tile_range(min_lat, min_lon, max_lat, max_lon, zoom);
+        let step = chunk_tiles as usize;
+
+        let mut chunks = Vec::new();
+        for x in (tx_min as i64..=tx_max as i64).step_by(step) {
+            for y in (ty_min as i64..=ty_max as i64).step_by(step) {
+                let x_end = (x + step as i64 - 1).min(tx_max as i64);
+                let y_end = (y + step as i64 - 1).min(ty_max as i64);
+                chunks.push((x as i32, y as i32, x_end as i32, y_end as i32));
+            }
+        }
+        chunks
+    }
+
+    /// Returns the number of tiles `tiles_for_bbox` would yield, without iterating them.
+    pub fn tile_count_for_bbox(&self, min_lat: T, min_lon: T, max_lat: T, max_lon: T, zoom: u32) -> u64 {
+        let (tx_min, ty_min, tx_max, ty_max) = self.bbox_tile_range(min_lat, min_lon, max_lat, max_lon, zoom);
+        (tx_max as i64 - tx_min as i64 + 1) as u64 * (ty_max as i64 - ty_min as i64 + 1) as u64
+    }
+
+    /// Returns the deepest zoom, up to 30, at which the lat/lon rectangle spans at most
+    /// `max_tiles` tiles, or 0 if even the root tile exceeds the budget.
+    pub fn zoom_for_tile_budget(&self, min_lat: T, min_lon: T, max_lat: T, max_lon: T, max_tiles: u64) -> u32 {
+        (1..=30)
+            .take_while(|&zoom| self.tile_count_for_bbox(min_lat, min_lon, max_lat, max_lon, zoom) <= max_tiles)
+            .last()
+            .unwrap_or(0)
+    }
+
+    /// Returns the deepest tile, as `(tx, ty, zoom)`, containing the whole lat/lon rectangle.
+    ///
+    /// Zooms are searched from 30 down, ending at the zoom 0 root tile for rectangles
+    /// straddling the origin.
+    pub fn enclosing_tile(&self, min_lat: T, min_lon: T, max_lat: T, max_lon: T) -> (i32, i32, u32) {
+        (0..=30)
+            .rev()
+            .find_map(|zoom| {
+                let (tx_min, ty_min, tx_max, ty_max) = self.bbox_tile_range(min_lat, min_lon, max_lat, max_lon, zoom);
+                if tx_min == tx_max && ty_min == ty_max {
+                    Some((tx_min, ty_min, zoom))
+                } else {
+                    None
+                }
+            })
+            .unwrap_or((0, 0, 0))
+    }
+
+    fn bbox_tile_range(&self, min_lat: T, min_lon: T, max_lat: T, max_lon: T, zoom: u32) -> (i32, i32, i32, i32) {
+        let (min_lat, max_lat) = if min_lat > max_lat { (max_lat, min_lat) } else { (min_lat, max_lat) };
+        let (min_lon, max_lon) = if min_lon > max_lon { (max_lon, min_lon) } else { (min_lon, max_lon) };
+
+        let (minx, miny) = self.lat_lon_to_meters(min_lat, min_lon);
+        let (maxx, maxy) = self.lat_lon_to_meters(max_lat, max_lon);
+        let (tx_min, ty_south) = self.meters_to_tile(minx, miny, zoom);
+        let (tx_max, ty_north) = self.meters_to_tile(maxx, maxy, zoom);
+        let (ty_min, ty_max) = (ty_south.min(ty_north), ty_south.max(ty_north));
+
+        let (tx_min, ty_min) = self.clamp_tile(tx_min, ty_min, zoom);
+        let (tx_max, ty_max) = self.clamp_tile(tx_max, ty_max, zoom);
+        (tx_min, ty_min, tx_max, ty_max)
+    }
+
+    pub fn tile_bounds(&self, tx: i32, ty: i32, zoom: u32) -> (T, T, T, T) {
         // "Returns bounds of the given tile in EPSG:900913 coordinates"
 
-        let (minx, miny) = self.pixels_to_meters((tx * self.tile_size as i32) as f64, (ty * self.tile_size as i32) as f64, zoom);
-        let (maxx, maxy) = self.pixels_to_meters(((tx + 1) * self.tile_size as i32) as f64, ((ty + 1) * self.tile_size as i32) as f64, zoom);
-        return (minx, miny, maxx, maxy);
+        self.tile_bounds_in(tx, ty, zoom, Crs::Meters)
     }
 
-    pub fn tile_lat_lon_bounds(&self, tx: i32, ty: i32, zoom: u32) -> (f64, f64, f64, f64) {
+    pub fn tile_lat_lon_bounds(&self, tx: i32, ty: i32, zoom: u32) -> (T, T, T, T) {
         // "Returns bounds of the given tile in latutude/longitude using WGS84 datum"
 
-        let (minx, miny, maxx, maxy) = self.tile_bounds(tx, ty, zoom);
+        let (min_lon, min_lat, max_lon, max_lat) = self.tile_bounds_in(tx, ty, zoom, Crs::Degrees);
+        (min_lat, min_lon, max_lat, max_lon)
+    }
+
+    /// Returns the bounds of the given tile in `crs`, as `(min_x, min_y, max_x, max_y)`.
+    ///
+    /// Both coordinate systems use x then y, so `Crs::Degrees` gives
+    /// `(min_lon, min_lat, max_lon, max_lat)`, unlike the lat first `tile_lat_lon_bounds`.
+    pub fn tile_bounds_in(&self, tx: i32, ty: i32, zoom: u32, crs: Crs) -> (T, T, T, T) {
+        let ty = self.origin_row(ty, zoom);
+        let tile_size = self.tile_size as f64;
+        let (tx, ty) = (tx as f64, ty as f64);
+        let (minx, miny) = self.pixels_to_meters(T::from_f64(tx * tile_size), T::from_f64(ty * tile_size), zoom);
+        let (maxx, maxy) = self.pixels_to_meters(T::from_f64((tx + 1.0) * tile_size), T::from_f64((ty + 1.0) * tile_size), zoom);
+
+        match crs {
+            Crs::Meters => (minx, miny, maxx, maxy),
+            Crs::Degrees => {
+                let (min_lat, min_lon) = self.meters_to_lat_lon(minx, miny);
+                let (max_lat, max_lon) = self.meters_to_lat_lon(maxx, maxy);
+                (min_lon, min_lat, max_lon, max_lat)
+            }
+        }
+    }
+
+    /// Returns the square extent of the whole map in EPSG:900913 coordinates, `±origin_shift` on
+    /// both axes, i.e. the bounds of the zoom 0 tile.
+    pub fn world_extent_meters(&self) -> (T, T, T, T) {
+        (-self.origin_shift, -self.origin_shift, self.origin_shift, self.origin_shift)
+    }
+
+    /// Returns the extent of the whole map as `(min_lat, min_lon, max_lat, max_lon)`. The
+    /// latitudes are the `±MAX_LATITUDE` cutoff of the square extent, up to rounding.
+    pub fn world_extent_lat_lon(&self) -> (T, T, T, T) {
+        let (minx, miny, maxx, maxy) = self.world_extent_meters();
         let (min_lat, min_lon) = self.meters_to_lat_lon(minx, miny);
         let (max_lat, max_lon) = self.meters_to_lat_lon(maxx, maxy);
+        (min_lat, min_lon, max_lat, max_lon)
+    }
 
-        return (min_lat, min_lon, max_lat, max_lon);
+    /// Returns the latitude cutoff of this pyramid, where `my == origin_shift`, by inverting the
+    /// Mercator formula.
+    ///
+    /// `origin_shift` scales with the radius, so the cutoff is `MAX_LATITUDE` (up to rounding)
+    /// whatever the radius or tile size.
+    pub fn max_latitude(&self) -> T {
+        self.meters_to_lat_lon(T::from_f64(0.0), self.origin_shift).0
     }
 
-    pub fn resolution(&self, zoom: u32) -> f64 {
-        // "resolution (meters/pixel) for given zoom level (measured at Equator)"
+    /// Returns `tile_bounds` grown by `buffer_px` pixels of the tile's zoom on every side, to
+    /// render tiles with some overlap and avoid seams at their edges.
+    ///
+    /// The buffered bounds reach past the extent of the map for edge tiles.
+    pub fn tile_bounds_buffered(&self, tx: i32, ty: i32, zoom: u32, buffer_px: T) -> (T, T, T, T) {
+        let buffer = buffer_px * self.resolution(zoom);
+        let (minx, miny, maxx, maxy) = self.tile_bounds(tx, ty, zoom);
+        (minx - buffer, miny - buffer, maxx + buffer, maxy + buffer)
+    }
 
-        // return (2 * PI * 6378137) / (self.tile_size * 2**zoom)
-        return self.initial_resolution / f64::powi(2.0, zoom as i32);
+    /// Returns the center of the given tile in EPSG:900913 coordinates.
+    pub fn tile_center_meters(&self, tx: i32, ty: i32, zoom: u32) -> (T, T) {
+        let two = T::from_f64(2.0);
+        let (minx, miny, maxx, maxy) = self.tile_bounds(tx, ty, zoom);
+        ((minx + maxx) / two, (miny + maxy) / two)
     }
 
-    pub fn zoom_for_pixel_size(&self, pixel_size: f64) -> u32 {
-        // "Maximal scaledown zoom of the pyramid closest to the pixel_size."
+    /// Returns the center of the given tile as lat/lon.
+    ///
+    /// This is the metric center reprojected to degrees. Because Mercator stretches latitudes
+    /// away from the equator it is not the average of the lat bounds, which is skewed towards the
+    /// equator.
+    pub fn tile_center_lat_lon(&self, tx: i32, ty: i32, zoom: u32) -> (T, T) {
+        let (mx, my) = self.tile_center_meters(tx, ty, zoom);
+        self.meters_to_lat_lon(mx, my)
+    }
 
-        for i in 0..30 {
-            if pixel_size > self.resolution(i) {
-                return if i != 0 {
-                    i - 1
-                } else {
-                    0 // We don't want to scale up
-                };
+    /// Returns the corners of the given tile in EPSG:900913 coordinates, counter-clockwise
+    /// starting from the north-west corner: north-west, south-west, south-east, north-east.
+    pub fn tile_corners_meters(&self, tx: i32, ty: i32, zoom: u32) -> [(T, T); 4] {
+        let (minx, miny, maxx, maxy) = self.tile_bounds(tx, ty, zoom);
+        [(minx, maxy), (minx, miny), (maxx, miny), (maxx, maxy)]
+    }
+
+    /// Same as `tile_corners_meters` as `(lat, lon)`, in the same order: north-west,
+    /// south-west, south-east, north-east.
+    pub fn tile_corners_lat_lon(&self, tx: i32, ty: i32, zoom: u32) -> [(T, T); 4] {
+        let (min_lat, min_lon, max_lat, max_lon) = self.tile_lat_lon_bounds(tx, ty, zoom);
+        [(max_lat, min_lon), (min_lat, min_lon), (min_lat, max_lon), (max_lat, max_lon)]
+    }
+
+    /// Returns the outline of the given tile as a closed ring of `(lat, lon)` points, with
+    /// `points_per_edge` points evenly spaced in Mercator meters along each edge, for
+    /// reprojecting the footprint to another CRS.
+    ///
+    /// Each edge contributes its starting corner and the points before the next corner, so the
+    /// ring has `4 * points_per_edge` distinct points, plus the first repeated at the end. It runs
+    /// in the order of `tile_corners_meters`. `points_per_edge` of 0 is treated as 1, the
+    /// corners alone.
+    #[cfg(feature = "alloc")]
+    pub fn tile_densified_ring(&self, tx: i32, ty: i32, zoom: u32, points_per_edge: usize) -> Vec<(T, T)> {
+        let points_per_edge = points_per_edge.max(1);
+        let corners = self.tile_corners_meters(tx, ty, zoom);
+
+        let mut ring = Vec::with_capacity(4 * points_per_edge + 1);
+        for (i, &(x1, y1)) in corners.iter().enumerate() {
+            let (x2, y2) = corners[(i + 1) % 4];
+            for step in 0..points_per_edge {
+                let t = T::from_f64(step as f64 / points_per_edge as f64);
+                ring.push(self.meters_to_lat_lon(x1 + (x2 - x1) * t, y1 + (y2 - y1) * t));
             }
         }
+        ring.push(ring[0]);
+        ring
+    }
 
-        panic!("Invalid pixel_size: {}", pixel_size);
+    /// Returns the lat/lon of every pixel center of the given tile, row by row from the
+    /// north-west pixel, e.g. to resample imagery into the tile.
+    ///
+    /// That's `tile_size * tile_size` points, 65536 for 256 pixel tiles, each costing a
+    /// `meters_to_lat_lon`. Use `tile_pixel_grid_lat_lon_iter` to avoid the allocation.
+    #[cfg(feature = "alloc")]
+    pub fn tile_pixel_grid_lat_lon(&self, tx: i32, ty: i32, zoom: u32) -> Vec<(T, T)> {
+        let mut grid = Vec::with_capacity(self.tile_size as usize * self.tile_size as usize);
+        grid.extend(self.tile_pixel_grid_lat_lon_iter(tx, ty, zoom));
+        grid
     }
 
-    pub fn google_tile(&self, tx: i32, ty: i32, zoom: u32) -> (i32, i32) {
-        // "Converts TMS tile coordinates to Google Tile coordinates"
+    /// Same as `tile_pixel_grid_lat_lon`, computing the points lazily.
+    pub fn tile_pixel_grid_lat_lon_iter(&self, tx: i32, ty: i32, zoom: u32) -> impl Iterator<Item = (T, T)> {
+        let mercator = *self;
+        let (minx, _, _, maxy) = self.tile_bounds(tx, ty, zoom);
+        let res = self.resolution(zoom);
+        let half = T::from_f64(0.5);
 
-        // coordinate origin is moved from bottom-left to top-left corner of the extent
-        return (tx, (f64::powi(2.0, zoom as i32) as i32 - 1) - ty);
+        (0..self.tile_size).flat_map(move |row| {
+            let my = maxy - (T::from_f64(row as f64) + half) * res;
+            (0..mercator.tile_size).map(move |col| mercator.meters_to_lat_lon(minx + (T::from_f64(col as f64) + half) * res, my))
+        })
     }
 
-    pub fn quad_tree(&self, tx: i32, ty: i32, zoom: u32) -> String {
-        // "Converts TMS tile coordinates to Microsoft quad_tree"
+    /// Great-circle distance in meters between two lat/lon points, on the sphere of `radius`.
+    pub fn haversine_distance(&self, lat1: T, lon1: T, lat2: T, lon2: T) -> T {
+        let (two, to_radians) = (T::from_f64(2.0), T::PI / T::from_f64(180.0));
+        let (lat1, lat2) = (lat1 * to_radians, lat2 * to_radians);
+        let half_dlat = (lat2 - lat1) / two;
+        let half_dlon = (lon2 - lon1) * to_radians / two;
 
-        let mut quad_key = String::new();
-        let ty = (f64::powi(2.0, zoom as i32) - 1.0) as i32 - ty;
-        for i in (0..zoom as i32).rev() {
-            let mut digit = 0;
-            let mask = 1 << (i - 1);
-            if (tx & mask) != 0 {
-                digit += 1;
-            }
-            if (ty & mask) != 0 {
-                digit += 2;
+        let a = half_dlat.sin() * half_dlat.sin() + lat1.cos() * lat2.cos() * half_dlon.sin() * half_dlon.sin();
+        two * self.radius * a.sqrt().asin()
+    }
+
+    /// Great-circle distance in meters between the south-west and north-east corners of the
+    /// given tile, e.g. for level of detail heuristics.
+    pub fn tile_diagonal_meters(&self, tx: i32, ty: i32, zoom: u32) -> T {
+        let (min_lat, min_lon, max_lat, max_lon) = self.tile_lat_lon_bounds(tx, ty, zoom);
+        self.haversine_distance(min_lat, min_lon, max_lat, max_lon)
+    }
+
+    /// Surface area in square meters the given tile covers on the sphere.
+    ///
+    /// Unlike the area on the map, which is the same for every tile of a zoom, this shrinks
+    /// towards the poles.
+    pub fn tile_ground_area(&self, tx: i32, ty: i32, zoom: u32) -> T {
+        let to_radians = T::PI / T::from_f64(180.0);
+        let (min_lat, min_lon, max_lat, max_lon) = self.tile_lat_lon_bounds(tx, ty, zoom);
+        let width = (max_lon - min_lon) * to_radians;
+        self.radius * self.radius * width * ((max_lat * to_radians).sin() - (min_lat * to_radians).sin())
+    }
+
+    /// Returns the tile of `target` at `zoom` containing the center of the given tile.
+    ///
+    /// The tile grid at a zoom only depends on the zoom, not the tile size: a 512 pixel tile
+    /// covers the same ground as the 256 pixel tile with the same indices, it just has twice the
+    /// resolution of its 256 pixel counterpart, i.e. that of the four 256 pixel tiles one zoom
+    /// deeper. So between pyramids that only differ by tile size this returns the same indices;
+    /// it is useful when `target` has a different `Origin` or radius. For the tiles of matching
+    /// resolution use `child_tiles` (or their children) of the result.
+    pub fn reproject_tile(&self, tx: i32, ty: i32, zoom: u32, target: &GlobalMercator<T>) -> (i32, i32) {
+        let (mx, my) = self.tile_center_meters(tx, ty, zoom);
+        target.meters_to_tile(mx, my, zoom)
+    }
+
+    /// Moves a lat/lon onto a corner of the tile grid at `zoom`, returned as `(lat, lon)`.
+    ///
+    /// Points already on a grid line (up to rounding errors) stay on it, so snapping is
+    /// idempotent with either mode.
+    pub fn snap_to_tile_grid(&self, lat: T, lon: T, zoom: u32, snap: CornerSnap) -> (T, T) {
+        let (px, py) = self.lat_lon_to_pixels(lat, lon, zoom);
+        let tile_size = T::from_f64(self.tile_size as f64);
+        let (x, y) = (px / tile_size, py / tile_size);
+
+        let (x, y) = match snap {
+            CornerSnap::NorthWest => {
+                let tolerance = T::from_f64(1e-9);
+                ((x + tolerance).floor(), (y - tolerance).ceil())
             }
-            quad_key.push_str(format!("{}", digit).as_str());
+            CornerSnap::Nearest => (x.round(), y.round()),
+        };
+        self.pixels_to_lat_lon(x * tile_size, y * tile_size, zoom)
+    }
+
+    /// Same as `tile_bounds`, returned as a `Bounds`.
+    pub fn tile_bounds_struct(&self, tx: i32, ty: i32, zoom: u32) -> Bounds<T> {
+        let (minx, miny, maxx, maxy) = self.tile_bounds(tx, ty, zoom);
+        Bounds::new(minx, miny, maxx, maxy)
+    }
+
+    /// Same as `tile_lat_lon_bounds`, returned as a `Bounds` with `x` as longitude and `y` as latitude.
+    pub fn tile_lat_lon_bounds_struct(&self, tx: i32, ty: i32, zoom: u32) -> Bounds<T> {
+        let (min_lat, min_lon, max_lat, max_lon) = self.tile_lat_lon_bounds(tx, ty, zoom);
+        Bounds::new(min_lon, min_lat, max_lon, max_lat)
+    }
+
+    /// Same as `tile_bounds`, as the corners `[[min_x, min_y], [max_x, max_y]]` for array
+    /// libraries.
+    pub fn tile_bounds_array(&self, tx: i32, ty: i32, zoom: u32) -> [[T; 2]; 2] {
+        let (minx, miny, maxx, maxy) = self.tile_bounds(tx, ty, zoom);
+        [[minx, miny], [maxx, maxy]]
+    }
+
+    /// Same as `tile_lat_lon_bounds`, as the corners `[[min_lat, min_lon], [max_lat, max_lon]]`.
+    pub fn tile_lat_lon_bounds_array(&self, tx: i32, ty: i32, zoom: u32) -> [[T; 2]; 2] {
+        let (min_lat, min_lon, max_lat, max_lon) = self.tile_lat_lon_bounds(tx, ty, zoom);
+        [[min_lat, min_lon], [max_lat, max_lon]]
+    }
+
+    /// Returns the pixel window `(x_off, y_off, width, height)` of the given tile overlapped by
+    /// `bbox` (degrees, `x` as longitude and `y` as latitude), or `None` if they don't overlap.
+    ///
+    /// Offsets are tile-local pixels from the top-left corner of the tile, as in the tile image.
+    /// Partially covered pixels are included in the window.
+    pub fn tile_bbox_pixel_window(&self, tx: i32, ty: i32, zoom: u32, bbox: Bounds<T>) -> Option<(u32, u32, u32, u32)> {
+        let (min_px, min_py) = self.lat_lon_to_pixels(bbox.min_y, bbox.min_x, zoom);
+        let (max_px, max_py) = self.lat_lon_to_pixels(bbox.max_y, bbox.max_x, zoom);
+
+        // Tile-local pixels, with y flipped to grow downwards from the top edge of the tile
+        let tile_size = T::from_f64(self.tile_size as f64);
+        let left = T::from_f64(tx as f64) * tile_size;
+        let top = T::from_f64((self.origin_row(ty, zoom) + 1) as f64) * tile_size;
+        let clamp = |p: T| if p < T::from_f64(0.0) { T::from_f64(0.0) } else if p > tile_size { tile_size } else { p };
+
+        let x0 = clamp(min_px - left).floor().to_i32();
+        let x1 = clamp(max_px - left).ceil().to_i32();
+        let y0 = clamp(top - max_py).floor().to_i32();
+        let y1 = clamp(top - min_py).ceil().to_i32();
+
+        if x0 >= x1 || y0 >= y1 {
+            return None;
         }
+        Some((x0 as u32, y0 as u32, (x1 - x0) as u32, (y1 - y0) as u32))
+    }
+
+    pub fn resolution(&self, zoom: u32) -> T {
+        // "resolution (meters/pixel) for given zoom level (measured at Equator)"
 
-        return quad_key;
+        if zoom < self.cached_zooms {
+            return self.resolutions[zoom as usize];
+        }
+        self.compute_resolution(zoom)
     }
-}
 
-// TODO: Implement GlobalGeodetic
-// TODO: Add tests
+    fn compute_resolution(&self, zoom: u32) -> T {
+        // return (2 * PI * 6378137) / (self.tile_size * 2**zoom)
+        self.initial_resolution / T::from_f64(2.0).powi(zoom as i32)
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Returns `resolution(from_zoom) / resolution(to_zoom)`, i.e. `2^(to_zoom - from_zoom)`: how
+    /// many `to_zoom` pixels (or tiles) span one `from_zoom` pixel along each axis.
+    pub fn zoom_ratio(&self, from_zoom: u32, to_zoom: u32) -> T {
+        T::from_f64(2.0).powi(to_zoom as i32 - from_zoom as i32)
+    }
 
-    const EPSILON_SCALE: f64 = 7_000_000.0; // TODO: This precision sucks
+    /// Factor to scale the images of `tile_zoom` tiles by, e.g. in a CSS transform, to display
+    /// them at the fractional `view_zoom`: `2^(view_zoom - tile_zoom)`.
+    pub fn tile_scale_factor(&self, tile_zoom: u32, view_zoom: T) -> T {
+        T::from_f64(2.0).powf(view_zoom - T::from_f64(tile_zoom as f64))
+    }
 
-    #[test]
-    fn test_default() {
-        assert_eq!(GlobalMercator::default().tile_size, 256);
+    /// Meters on the ground per pixel at latitude `lat`, i.e. `resolution` scaled by `cos(lat)`.
+    ///
+    /// `zoom` may be fractional. Latitudes are clamped to `±MAX_LATITUDE`, the edge of the map.
+    pub fn ground_resolution(&self, lat: T, zoom: T) -> T {
+        let lat = clamp_latitude(lat) * T::PI / T::from_f64(180.0);
+        self.fractional_resolution(zoom) * lat.cos()
     }
 
-    #[test]
-    fn test_new() {
-        assert_eq!(GlobalMercator::new(256).tile_size, 256);
+    /// How many pixels a feature `length_m` meters long on the ground spans at latitude `lat`
+    /// and the (possibly fractional) `zoom`, using `ground_resolution`.
+    pub fn meters_to_pixels_length(&self, length_m: T, lat: T, zoom: T) -> T {
+        length_m / self.ground_resolution(lat, zoom)
     }
 
-    #[test]
-    fn test_lat_lon_meters() {
-        let mercator = GlobalMercator::default();
-        let (lat, lon) = (3.2, 4.22);
+    /// Ground length in meters of `length_px` pixels at latitude `lat` and `zoom`, the inverse of
+    /// `meters_to_pixels_length`.
+    pub fn pixels_to_meters_length(&self, length_px: T, lat: T, zoom: T) -> T {
+        length_px * self.ground_resolution(lat, zoom)
+    }
 
-        let (mx, my) = mercator.lat_lon_to_meters(lat, lon);
-        let (lat_new, lon_new) = mercator.meters_to_lat_lon(mx, my);
+    /// Web Mercator point scale factor `k = 1 / cos(lat)`: how many times distances at `lat` are
+    /// exaggerated on the map. Independent of zoom and tile size.
+    ///
+    /// `k` is 1 on the equator, 2 at 60° and diverges towards the poles, reaching about 11.6 at
+    /// `MAX_LATITUDE` and infinity (or a huge number, due to rounding) at ±90°.
+    pub fn point_scale_factor(&self, lat: T) -> T {
+        T::from_f64(1.0) / (lat * T::PI / T::from_f64(180.0)).cos()
+    }
 
+    /// Ground meters spanned by one degree of `(longitude, latitude)` at latitude `lat`, on the
+    /// sphere of `radius`, for local planar approximations.
+    ///
+    /// A degree of latitude is the same everywhere on the sphere, while a degree of longitude
+    /// shrinks by `cos(lat)`. See `mercator_meters_per_degree` for distances on the map.
+    pub fn meters_per_degree(&self, lat: T) -> (T, T) {
+        let degree = self.radius * T::PI / T::from_f64(180.0);
+        (degree * (lat * T::PI / T::from_f64(180.0)).cos(), degree)
+    }
 
-        assert!((lat - lat_new).abs() < std::f64::EPSILON * EPSILON_SCALE, "failed to compare: {} != {}, (lat - lat_new).abs() = {}, std::f64::EPSILON = {}", lat, lat_new, (lat - lat_new).abs(), std::f64::EPSILON * EPSILON_SCALE);
-        assert!((lon - lon_new).abs() < std::f64::EPSILON * EPSILON_SCALE, "failed to compare: {} != {}, (lon - lon_new).abs() = {}, std::f64::EPSILON = {}", lon, lon_new, (lon - lon_new).abs(), std::f64::EPSILON * EPSILON_SCALE);
+    /// Mercator meters (EPSG:900913) spanned by one degree of `(longitude, latitude)` at
+    /// latitude `lat`.
+    ///
+    /// On the map a degree of longitude is `origin_shift / 180` everywhere, while a degree of
+    /// latitude grows by the `point_scale_factor` away from the equator.
+    pub fn mercator_meters_per_degree(&self, lat: T) -> (T, T) {
+        let degree = self.origin_shift / T::from_f64(180.0);
+        (degree, degree * self.point_scale_factor(lat))
     }
 
-    #[test]
-    fn test_meters_pixels() {
-        let mercator = GlobalMercator::default();
-        let (mx, my) = (31100.00, 42200.1);
-        let zoom = 8;
+    fn fractional_resolution(&self, zoom: T) -> T {
+        self.initial_resolution / T::from_f64(2.0).powf(zoom)
+    }
 
-        let (px, py) = mercator.meters_to_pixels(mx, my, zoom);
-        let (mx_new, my_new) = mercator.pixels_to_meters(px, py, zoom);
+    /// Returns the resolution in meters/pixel at which the longitudes `min_lon..max_lon` span
+    /// exactly `pixels` pixels, e.g. to render an image of a given width.
+    ///
+    /// This only looks at the x axis: the extent is converted to Mercator meters on the equator,
+    /// and the height of the image follows from the latitudes and the Mercator stretching.
+    pub fn resolution_for_extent_pixels(&self, min_lon: T, max_lon: T, pixels: T) -> T {
+        let zero = T::from_f64(0.0);
+        let (minx, _) = self.lat_lon_to_meters(zero, min_lon);
+        let (maxx, _) = self.lat_lon_to_meters(zero, max_lon);
+        (maxx - minx).abs() / pixels
+    }
 
-        assert!((mx - mx_new).abs() < std::f64::EPSILON * EPSILON_SCALE, "failed to compare: {} != {}, (mx - mx_new).abs() = {}, std::f64::EPSILON = {}", mx, mx_new, (mx - mx_new).abs() , std::f64::EPSILON * EPSILON_SCALE);
-        assert!((my - my_new).abs() < std::f64::EPSILON * EPSILON_SCALE, "failed to compare: {} != {}, (my - my_new).abs() = {}, std::f64::EPSILON = {}", my, my_new, (my - my_new).abs(), std::f64::EPSILON * EPSILON_SCALE);
+    /// Returns the continuous zoom whose resolution is exactly `resolution` meters/pixel, the
+    /// inverse of `resolution` for integer zooms.
+    ///
+    /// Resolutions coarser than zoom 0 give a negative zoom.
+    pub fn fractional_zoom_for_resolution(&self, resolution: T) -> T {
+        (self.initial_resolution / resolution).log2()
     }
 
-    // TODO: pixels_to_tile is wrong, it should be using 'floor' not 'ceil' - 1 because of we are on the min edge the divide is exact so -1 puts us in the wrong tile
-//    #[test]
-//    fn test_pixels_tile() {
-//        let mercator = GlobalMercator::default();
-//        let (px, py) = (0.0, 0.0);
-//        let zoom = 8;
-//
-//        let (tx, ty) = mercator.pixels_to_tile(px, py);
-//        let tile_size = mercator.tile_size();
-//        let (px_new, py_new) = ((tx * tile_size as i32) as f64, (ty * tile_size as i32) as f64);
-//
-//        assert_eq!(px, px_new);
-//        assert_eq!(py, py_new);
-//    }
+    /// OGC scale denominator of the given zoom, i.e. its resolution divided by the standardized
+    /// `WMTS_PIXEL_SIZE`.
+    pub fn scale_denominator(&self, zoom: u32) -> T {
+        self.resolution(zoom) / T::from_f64(WMTS_PIXEL_SIZE)
+    }
+
+    /// Returns the continuous zoom with the given OGC scale denominator, the inverse of
+    /// `scale_denominator`. Round it to snap to a zoom level.
+    pub fn zoom_for_scale_denominator(&self, scale_denominator: T) -> T {
+        self.fractional_zoom_for_resolution(scale_denominator * T::from_f64(WMTS_PIXEL_SIZE))
+    }
+
+    /// Number of tiles along each axis at the given zoom, i.e. `2^zoom`.
+    ///
+    /// Saturates at `u64::MAX` past zoom 63. Tile indices are `i32`, so zooms past 31 are only
+    /// meaningful to the methods that don't address single tiles; the others keep computing
+    /// instead of panicking, with rows and columns saturated into range.
+    pub fn num_tiles(&self, zoom: u32) -> u64 {
+        1u64.checked_shl(zoom).unwrap_or(u64::MAX)
+    }
+
+    // num_tiles as an i64 for index arithmetic, saturating at i64::MAX past zoom 62
+    fn grid_len(&self, zoom: u32) -> i64 {
+        self.num_tiles(zoom).min(i64::MAX as u64) as i64
+    }
+
+    /// Width and height of the whole map in pixels at the given zoom.
+    pub fn map_size(&self, zoom: u32) -> u64 {
+        (self.tile_size as u64) << zoom
+    }
+
+    pub fn zoom_for_pixel_size(&self, pixel_size: T) -> Result<u32, GlobalMercatorError> {
+        // "Maximal scaledown zoom of the pyramid closest to the pixel_size."
+
+        let searched = 0..30;
+        for i in searched.clone() {
+            if pixel_size > self.resolution(i) {
+                return Ok(if i != 0 {
+                    i - 1
+                } else {
+                    0 // We don't want to scale up
+                });
+            }
+        }
+
+        Err(GlobalMercatorError::InvalidZoom {
+            pixel_size: pixel_size.to_f64(),
+            searched,
+        })
+    }
+
+    /// Returns how many zoom levels up from `from_zoom` the overviews must go to reach
+    /// `target_resolution` meters/pixel, picking the zoom like `zoom_for_pixel_size`.
+    ///
+    /// Targets finer than the resolution of `from_zoom` need no overviews and give 0.
+    pub fn zoom_depth_to_resolution(&self, from_zoom: u32, target_resolution: T) -> u32 {
+        // Pixel sizes finer than every searched zoom are at least as deep as the last one
+        let target_zoom = self.zoom_for_pixel_size(target_resolution).unwrap_or(29);
+        from_zoom.saturating_sub(target_zoom)
+    }
+
+    /// Returns the zoom best matching a viewport `viewport_px` pixels wide showing
+    /// `ground_width_m` meters, see `zoom_for_pixel_size`.
+    ///
+    /// The ground width is taken as Mercator meters, which only match real distances on the
+    /// equator; use `zoom_for_viewport_at_lat` elsewhere.
+    pub fn zoom_for_viewport(&self, ground_width_m: T, viewport_px: T) -> Result<u32, GlobalMercatorError> {
+        self.zoom_for_pixel_size(ground_width_m / viewport_px)
+    }
+
+    /// Same as `zoom_for_viewport` for a viewport centered on `lat`, with `ground_width_m`
+    /// measured on the ground.
+    ///
+    /// Mercator stretches distances by `1 / cos(lat)`, so the same ground width covers more
+    /// Mercator meters (and needs a lower zoom) the further the viewport is from the equator.
+    pub fn zoom_for_viewport_at_lat(&self, ground_width_m: T, viewport_px: T, lat: T) -> Result<u32, GlobalMercatorError> {
+        let lat = lat * T::PI / T::from_f64(180.0);
+        self.zoom_for_pixel_size(ground_width_m / viewport_px / lat.cos())
+    }
+
+    /// Returns the center `(lat, lon)` and the deepest zoom at which the given lat/lon rectangle
+    /// fits in a viewport of `(width, height)` pixels, to initialize a map view.
+    ///
+    /// The rectangle is measured in Mercator meters, so it accounts for the stretching away from
+    /// the equator, and its center is the center on the map rather than the average of the
+    /// latitudes. Latitudes are clamped to `±MAX_LATITUDE`, and rectangles too small for any
+    /// zoom of `zoom_for_pixel_size` give zoom 29.
+    pub fn fit_bounds(&self, min_lat: T, min_lon: T, max_lat: T, max_lon: T, viewport_px: (T, T)) -> (T, T, u32) {
+        let (x1, y1) = self.lat_lon_to_meters_clamped(min_lat, min_lon);
+        let (x2, y2) = self.lat_lon_to_meters_clamped(max_lat, max_lon);
+        let (width, height) = ((x2 - x1).abs(), (y2 - y1).abs());
+
+        let (by_width, by_height) = (width / viewport_px.0, height / viewport_px.1);
+        let pixel_size = if by_width > by_height { by_width } else { by_height };
+        let zoom = self.zoom_for_pixel_size(pixel_size).unwrap_or(29);
+
+        let two = T::from_f64(2.0);
+        let (lat, lon) = self.meters_to_lat_lon((x1 + x2) / two, (y1 + y2) / two);
+        (lat, lon, zoom)
+    }
+
+    /// Converts a screen offset of `(dx_px, dy_px)` pixels from the center of a map view showing
+    /// `(center_lat, center_lon)` at `zoom` to lat/lon, with y increasing downwards like on screen.
+    ///
+    /// `zoom` may be fractional. A zero offset returns the center exactly, other offsets are
+    /// applied in global pixels and reprojected.
+    pub fn viewport_pixel_to_lat_lon(&self, center_lat: T, center_lon: T, zoom: T, dx_px: T, dy_px: T) -> (T, T) {
+        let zero = T::from_f64(0.0);
+        if dx_px == zero && dy_px == zero {
+            return (center_lat, center_lon);
+        }
+
+        let (mx, my) = self.lat_lon_to_meters(center_lat, center_lon);
+        let (px, py) = self.meters_to_pixels_f(mx, my, zoom);
+        // pyramid pixels grow northwards, screen pixels southwards
+        let (mx, my) = self.pixels_to_meters_f(px + dx_px, py - dy_px, zoom);
+        self.meters_to_lat_lon(mx, my)
+    }
+
+    /// Returns `true` if both indices are inside the `2^zoom` by `2^zoom` grid.
+    pub fn is_valid_tile(&self, tx: i32, ty: i32, zoom: u32) -> bool {
+        let n = self.num_tiles(zoom);
+        tx >= 0 && ty >= 0 && (tx as u64) < n && (ty as u64) < n
+    }
+
+    /// Clamps both indices into `0..2^zoom`, e.g. after neighbor or parent arithmetic.
+    ///
+    /// Columns are clamped too; see `meters_to_tile_wrapped` to wrap them around the
+    /// antimeridian instead.
+    pub fn clamp_tile(&self, tx: i32, ty: i32, zoom: u32) -> (i32, i32) {
+        let last = (self.num_tiles(zoom) - 1).min(i32::MAX as u64) as i32;
+        (tx.clamp(0, last), ty.clamp(0, last))
+    }
+
+    /// Returns the tile one zoom level up containing the given tile, `None` at zoom 0.
+    ///
+    /// Halving the indices gives the same parent whether the rows are TMS or Google/XYZ, so this
+    /// works with either convention as long as input and output use the same one.
+    pub fn parent_tile(&self, tx: i32, ty: i32, zoom: u32) -> Option<(i32, i32, u32)> {
+        if zoom == 0 {
+            return None;
+        }
+        Some((tx >> 1, ty >> 1, zoom - 1))
+    }
+
+    /// Returns the tiles at `target_zoom` covering the given tiles at `zoom`, each listed once in
+    /// the order they are first reached.
+    ///
+    /// Like `parent_tile` this works with either row convention. `target_zoom` must not be
+    /// deeper than `zoom`.
+    #[cfg(feature = "alloc")]
+    pub fn parents_covering(&self, tiles: &[(i32, i32)], zoom: u32, target_zoom: u32) -> Vec<(i32, i32)> {
+        debug_assert!(target_zoom <= zoom, "target zoom {} is deeper than {}", target_zoom, zoom);
+        let levels = zoom.saturating_sub(target_zoom);
+
+        let mut seen = BTreeSet::new();
+        tiles.iter().map(|&(tx, ty)| (tx >> levels, ty >> levels)).filter(|&parent| seen.insert(parent)).collect()
+    }
+
+    /// Returns the four tiles one zoom level down covering the given tile.
+    ///
+    /// Like `parent_tile` this is independent of the row convention. The children are ordered by
+    /// row then column: `(2x, 2y)`, `(2x + 1, 2y)`, `(2x, 2y + 1)`, `(2x + 1, 2y + 1)`.
+    ///
+    /// Zoom 32 indices don't fit in `i32`, so this panics if `zoom` is past 30 or if the children
+    /// of an out of range tile would overflow.
+    pub fn child_tiles(&self, tx: i32, ty: i32, zoom: u32) -> [(i32, i32, u32); 4] {
+        assert!(zoom <= 30, "zoom {} tiles have no children with i32 indices", zoom);
+        let double = |t: i32| {
+            t.checked_mul(2).filter(|&c| c != i32::MAX).expect("tile has no children with i32 indices")
+        };
+        let (x, y, zoom) = (double(tx), double(ty), zoom + 1);
+        [(x, y, zoom), (x + 1, y, zoom), (x, y + 1, zoom), (x + 1, y + 1, zoom)]
+    }
+
+    /// Flips a tile row between TMS (counted from the bottom) and Google/XYZ (counted from the
+    /// top), i.e. `2^zoom - 1 - ty`. Flipping twice is the identity.
+    ///
+    /// The subtraction is done in 64 bits, so zoom 31 rows don't overflow. Like `google_to_tms`,
+    /// rows outside `0..2^zoom` are mirrored rather than rejected, saturating at the `i32` range.
+    pub fn flip_tile_y(&self, ty: i32, zoom: u32) -> i32 {
+        self.flip_row(ty as i64, zoom).clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    }
+
+    fn flip_row(&self, row: i64, zoom: u32) -> i64 {
+        (self.grid_len(zoom) - 1).saturating_sub(row)
+    }
+
+    /// At zoom 0 the grid is the single root tile, whose row is 0 in both schemes: `(0, 0)` maps
+    /// to itself. Other tiles at zoom 0 are rejected by a debug assertion; use `try_google_tile`
+    /// to get an error instead.
+    pub fn google_tile(&self, tx: i32, ty: i32, zoom: u32) -> (i64, i64) {
+        // "Converts TMS tile coordinates to Google Tile coordinates"
+
+        debug_assert!(zoom != 0 || (tx, ty) == (0, 0), "tile ({}, {}) doesn't exist at zoom 0", tx, ty);
+        // coordinate origin is moved from bottom-left to top-left corner of the extent
+        (tx as i64, self.flip_row(ty as i64, zoom))
+    }
+
+    /// Checked version of `google_tile` rejecting tiles outside the `2^zoom` by `2^zoom` grid,
+    /// e.g. anything but `(0, 0)` at zoom 0.
+    pub fn try_google_tile(&self, tx: i32, ty: i32, zoom: u32) -> Result<(i64, i64), GlobalMercatorError> {
+        if !self.is_valid_tile(tx, ty, zoom) {
+            return Err(GlobalMercatorError::TileOutOfRange { tx, ty, zoom });
+        }
+        Ok(self.google_tile(tx, ty, zoom))
+    }
+
+    /// Converts Google Tile coordinates back to TMS tile coordinates.
+    ///
+    /// The row flip is the same as in `google_tile`, so applying either function twice is the
+    /// identity. Inputs are not validated: a row outside `0..2^zoom` (including negative rows)
+    /// is mirrored to the opposite side of the grid instead of being rejected, e.g. `gy = -1`
+    /// becomes `ty = 2^zoom`.
+    pub fn google_to_tms(&self, gx: i32, gy: i32, zoom: u32) -> (i32, i32) {
+        (gx, self.flip_tile_y(gy, zoom))
+    }
+
+    /// Converts TMS tile coordinates to the WMTS `(TileCol, TileRow)` of the GoogleMapsCompatible
+    /// matrix at `zoom`, counting rows from the top like `google_tile`.
+    ///
+    /// Returns `None` for tiles outside the `2^zoom` by `2^zoom` matrix.
+    pub fn tms_to_wmts(&self, tx: i32, ty: i32, zoom: u32) -> Option<(u32, u32)> {
+        let n = self.num_tiles(zoom);
+        if tx < 0 || ty < 0 || tx as u64 >= n || ty as u64 >= n {
+            return None;
+        }
+        Some((tx as u32, (n - 1 - ty as u64) as u32))
+    }
+
+    /// Converts a WMTS `(TileCol, TileRow)` back to TMS tile coordinates, the inverse of
+    /// `tms_to_wmts`.
+    ///
+    /// Returns `None` for tiles outside the `2^zoom` by `2^zoom` matrix.
+    pub fn wmts_to_tms(&self, col: u32, row: u32, zoom: u32) -> Option<(i32, i32)> {
+        let n = self.num_tiles(zoom);
+        if col as u64 >= n || row as u64 >= n {
+            return None;
+        }
+        Some((col as i32, (n - 1 - row as u64) as i32))
+    }
+
+    /// The quad key has one digit per zoom level, so the zoom 0 root tile gives the empty string,
+    /// which `quad_tree_to_tile` doesn't accept back. Other tiles at zoom 0 are rejected by a debug
+    /// assertion since the empty key would silently drop their indices; use `try_quad_tree` to get
+    /// an error instead.
+    ///
+    /// Tile indices are `i32`, so zooms past 31 aren't supported either and are rejected by a
+    /// debug assertion too.
+    #[cfg(feature = "alloc")]
+    pub fn quad_tree(&self, tx: i32, ty: i32, zoom: u32) -> String {
+        // "Converts TMS tile coordinates to Microsoft quad_tree"
+
+        debug_assert!(zoom != 0 || (tx, ty) == (0, 0), "tile ({}, {}) doesn't exist at zoom 0", tx, ty);
+        debug_assert!(zoom <= 31, "quad keys are limited to zoom 31, got {}", zoom);
+        let mut quad_key = String::new();
+        let ty = self.flip_tile_y(ty, zoom);
+        for i in (1..=zoom as i32).rev() {
+            let mut digit = 0;
+            let mask = 1 << (i - 1);
+            if (tx & mask) != 0 {
+                digit += 1;
+            }
+            if (ty & mask) != 0 {
+                digit += 2;
+            }
+            quad_key.push_str(alloc::format!("{}", digit).as_str());
+        }
+
+        quad_key
+    }
+
+    /// Checked version of `quad_tree` rejecting zooms past 31 and tiles outside the `2^zoom` by
+    /// `2^zoom` grid, whose quad key would address another tile. The zoom 0 root tile is accepted
+    /// and gives the empty string.
+    #[cfg(feature = "alloc")]
+    pub fn try_quad_tree(&self, tx: i32, ty: i32, zoom: u32) -> Result<String, GlobalMercatorError> {
+        let supported = 0..32;
+        if !supported.contains(&zoom) {
+            return Err(GlobalMercatorError::ZoomOutOfRange { zoom, supported });
+        }
+        if !self.is_valid_tile(tx, ty, zoom) {
+            return Err(GlobalMercatorError::TileOutOfRange { tx, ty, zoom });
+        }
+        Ok(self.quad_tree(tx, ty, zoom))
+    }
+
+    /// Same as `quad_tree`, restricted to the Bing Maps levels of detail.
+    ///
+    /// Bing quad keys have one digit per level from 1 to 23, so zoom 0 (which would be the empty
+    /// string) and zooms past 23 are rejected.
+    #[cfg(feature = "alloc")]
+    pub fn quad_key(&self, tx: i32, ty: i32, zoom: u32) -> Result<String, GlobalMercatorError> {
+        let supported = 1..24;
+        if !supported.contains(&zoom) {
+            return Err(GlobalMercatorError::ZoomOutOfRange { zoom, supported });
+        }
+
+        let gy = self.google_tile(tx, ty, zoom).1;
+        let mut quad_key = String::with_capacity(zoom as usize);
+        for i in (0..zoom).rev() {
+            let digit = ((tx >> i) & 1) as u8 + 2 * ((gy >> i) & 1) as u8;
+            quad_key.push(char::from(b'0' + digit));
+        }
+        Ok(quad_key)
+    }
+
+    /// Errors with the position of the first character other than `0`-`3`, or on an empty
+    /// string: the zoom 0 root tile has no quad key. Keys longer than 31 digits are rejected with
+    /// `ZoomOutOfRange`, since their tiles don't fit in `i32` indices.
+    pub fn quad_tree_to_tile(&self, quad_key: &str) -> Result<(i32, i32, u32), GlobalMercatorError> {
+        // "Converts Microsoft quad_tree to TMS tile coordinates"
+
+        if quad_key.is_empty() {
+            return Err(GlobalMercatorError::EmptyQuadKey);
+        }
+        let zoom = quad_key.chars().count() as u32;
+        let supported = 1..32;
+        if !supported.contains(&zoom) {
+            return Err(GlobalMercatorError::ZoomOutOfRange { zoom, supported });
+        }
+        let (mut tx, mut ty) = (0, 0);
+        for (i, c) in quad_key.chars().enumerate() {
+            let mask = 1 << (zoom as usize - i - 1);
+            match c {
+                '0' => {}
+                '1' => tx |= mask,
+                '2' => ty |= mask,
+                '3' => {
+                    tx |= mask;
+                    ty |= mask;
+                }
+                found => return Err(GlobalMercatorError::InvalidQuadKey { position: i, found }),
+            }
+        }
+
+        // quad keys use the Google origin, flip back to TMS
+        Ok((tx, self.flip_tile_y(ty, zoom), zoom))
+    }
+}
+
+// TODO: Add tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A million coordinates spread over the whole Mercator range, for the timing tests.
+    pub(crate) fn million_coords() -> Vec<(f64, f64)> {
+        (0..1_000_000).map(|i| ((i % 170) as f64 - 85.0, (i % 360) as f64 - 180.0)).collect()
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(GlobalMercator::default().tile_size, 256);
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(GlobalMercator::new(256).tile_size, 256);
+    }
+
+    #[test]
+    fn test_const_new() {
+        const MERCATOR: GlobalMercator = GlobalMercator::new(256);
+
+        let generic = GlobalMercator::<f64>::with_tile_size(256);
+        assert_eq!(MERCATOR.tile_size(), 256);
+        assert_eq!(MERCATOR.radius(), generic.radius());
+        assert_eq!(MERCATOR.initial_resolution.to_bits(), generic.initial_resolution.to_bits());
+        assert_eq!(MERCATOR.origin_shift.to_bits(), generic.origin_shift.to_bits());
+    }
+
+    #[test]
+    fn test_with_radius() {
+        let wgs84 = GlobalMercator::new(256);
+        let sphere = GlobalMercator::with_radius(256, 6371000.0);
+
+        assert_eq!(wgs84.radius(), WGS84_RADIUS);
+        assert_eq!(sphere.radius(), 6371000.0);
+
+        let ratio = sphere.resolution(0) / wgs84.resolution(0);
+        assert!((ratio - 6371000.0 / WGS84_RADIUS).abs() < f64::EPSILON * 4.0, "ratio = {}", ratio);
+
+        let (lat, lon) = (48.6, 2.3);
+        let (mx, my) = sphere.lat_lon_to_meters(lat, lon);
+        let (lat_new, lon_new) = sphere.meters_to_lat_lon(mx, my);
+        assert!(approx_eq_deg(lat, lat_new), "failed to compare: {} != {}", lat, lat_new);
+        assert!(approx_eq_deg(lon, lon_new), "failed to compare: {} != {}", lon, lon_new);
+    }
+
+    #[test]
+    fn test_dpi_scale() {
+        let standard = GlobalMercator::new(256);
+        let large = GlobalMercator::new(512);
+        let retina = GlobalMercator::<f64>::with_dpi_scale(256, 2);
+
+        assert_eq!((large.tile_size(), large.pixel_ratio()), (512, 1));
+        assert_eq!((retina.tile_size(), retina.pixel_ratio()), (512, 2));
+
+        // Bigger tiles cover more ground, high-DPI tiles just have more pixels
+        assert_eq!(large.resolution(5), standard.resolution(6));
+        for zoom in 0..20 {
+            assert_eq!(retina.resolution(zoom), standard.resolution(zoom));
+        }
+        assert_eq!(retina.tile_bounds(134, 90, 8), standard.tile_bounds(134, 90, 8));
+        assert_eq!(retina.lat_lon_to_tile(48.6, 2.3, 12), standard.lat_lon_to_tile(48.6, 2.3, 12));
+    }
+
+    #[test]
+    fn test_zoom_cache() {
+        let computed = GlobalMercator::new(256);
+        let cached: GlobalMercator = GlobalMercator::with_zoom_cache(256, 20);
+
+        assert_eq!(computed.max_cached_zoom(), None);
+        assert_eq!(cached.max_cached_zoom(), Some(20));
+        for zoom in 0..30 {
+            assert_eq!(cached.resolution(zoom).to_bits(), computed.resolution(zoom).to_bits(), "zoom {}", zoom);
+        }
+
+        let capped: GlobalMercator = GlobalMercator::with_zoom_cache(256, 100);
+        assert_eq!(capped.max_cached_zoom(), Some(ZOOM_CACHE_LEN as u32 - 1));
+        assert_eq!(capped.resolution(40).to_bits(), computed.resolution(40).to_bits());
+
+        let max: GlobalMercator = GlobalMercator::with_zoom_cache(256, u32::MAX);
+        assert_eq!(max.max_cached_zoom(), Some(ZOOM_CACHE_LEN as u32 - 1));
+    }
+
+    #[test]
+    fn test_lat_lon_meters() {
+        let mercator = GlobalMercator::default();
+        let (lat, lon) = (3.2, 4.22);
+
+        let (mx, my) = mercator.lat_lon_to_meters(lat, lon);
+        let (lat_new, lon_new) = mercator.meters_to_lat_lon(mx, my);
+
+
+        assert!(approx_eq_deg(lat, lat_new), "failed to compare: {} != {}, (lat - lat_new).abs() = {}", lat, lat_new, (lat - lat_new).abs());
+        assert!(approx_eq_deg(lon, lon_new), "failed to compare: {} != {}, (lon - lon_new).abs() = {}", lon, lon_new, (lon - lon_new).abs());
+    }
+
+    #[test]
+    fn test_f32_lat_lon_meters() {
+        let mercator = GlobalMercator::<f32>::with_tile_size(256);
+        let (lat, lon) = (48.6_f32, 2.3_f32);
+
+        let (mx, my) = mercator.lat_lon_to_meters(lat, lon);
+        let (lat_new, lon_new) = mercator.meters_to_lat_lon(mx, my);
+
+        // Looser than the f64 tolerance, f32 only has ~7 significant digits
+        assert!((lat - lat_new).abs() < 1e-4, "failed to compare: {} != {}", lat, lat_new);
+        assert!((lon - lon_new).abs() < 1e-4, "failed to compare: {} != {}", lon, lon_new);
+
+        let (mx64, my64) = GlobalMercator::default().lat_lon_to_meters(48.6, 2.3);
+        assert!((mx as f64 - mx64).abs() < 10.0, "failed to compare: {} != {}", mx, mx64);
+        assert!((my as f64 - my64).abs() < 10.0, "failed to compare: {} != {}", my, my64);
+    }
+
+    #[test]
+    fn test_f32_tiles() {
+        let mercator = GlobalMercator::<f32>::with_tile_size(256);
+        let (mx, my) = GlobalMercator::default().lat_lon_to_meters(48.6, 2.3);
+
+        assert_eq!(mercator.meters_to_tile(mx as f32, my as f32, 10), GlobalMercator::default().meters_to_tile(mx, my, 10));
+        assert!((mercator.resolution(0) as f64 - 156543.03392804062).abs() < 0.01, "{}", mercator.resolution(0));
+    }
+
+    #[test]
+    fn test_try_lat_lon_to_meters() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.try_lat_lon_to_meters(3.2, 4.22), Ok(mercator.lat_lon_to_meters(3.2, 4.22)));
+        assert_eq!(mercator.try_lat_lon_to_meters(0.0, -180.0), Ok(mercator.lat_lon_to_meters(0.0, -180.0)));
+
+        // The pole is a valid latitude, it just lies outside the Mercator square
+        let (_, my) = mercator.try_lat_lon_to_meters(90.0, 0.0).unwrap();
+        assert!(my > mercator.origin_shift);
+
+        assert_eq!(mercator.try_lat_lon_to_meters(95.0, 0.0), Err(GlobalMercatorError::InvalidLatitude(95.0)));
+        assert_eq!(mercator.try_lat_lon_to_meters(0.0, 181.0), Err(GlobalMercatorError::InvalidLongitude(181.0)));
+        assert!(mercator.try_lat_lon_to_meters(f64::NAN, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_lat_lon_to_meters_clamped() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.lat_lon_to_meters_clamped(89.0, 10.0), mercator.lat_lon_to_meters(MAX_LATITUDE, 10.0));
+        assert_eq!(mercator.lat_lon_to_meters_clamped(-90.0, 10.0), mercator.lat_lon_to_meters(-MAX_LATITUDE, 10.0));
+        assert_eq!(mercator.lat_lon_to_meters_clamped(48.6, 2.3), mercator.lat_lon_to_meters(48.6, 2.3));
+
+        // The cutoff is where the map becomes square
+        let (_, my) = mercator.lat_lon_to_meters(MAX_LATITUDE, 0.0);
+        assert!((my - mercator.origin_shift).abs() < 1.0, "{} != {}", my, mercator.origin_shift);
+    }
+
+    #[test]
+    fn test_is_in_mercator_extent() {
+        let mercator = GlobalMercator::default();
+
+        assert!(mercator.is_in_mercator_extent(85.0, 0.0));
+        assert!(!mercator.is_in_mercator_extent(86.0, 0.0));
+        assert!(mercator.is_in_mercator_extent(MAX_LATITUDE, 180.0));
+        assert!(mercator.is_in_mercator_extent(-MAX_LATITUDE, -180.0));
+        assert!(!mercator.is_in_mercator_extent(-86.0, 0.0));
+        assert!(!mercator.is_in_mercator_extent(0.0, 180.5));
+        assert!(!mercator.is_in_mercator_extent(f64::NAN, 0.0));
+
+        // So is the corner of the square map
+        let (lat, lon) = mercator.meters_to_lat_lon(mercator.origin_shift, mercator.origin_shift);
+        assert!(mercator.is_in_mercator_extent(lat, lon), "{}, {}", lat, lon);
+    }
+
+    #[test]
+    fn test_batch() {
+        let mercator = GlobalMercator::default();
+        let coords = [(3.2, 4.22), (48.6, 2.3), (-33.9, 151.2), (0.0, 0.0)];
+
+        let mut meters = [(0.0, 0.0); 4];
+        mercator.lat_lon_to_meters_batch(&coords, &mut meters);
+        let mut lat_lon = [(0.0, 0.0); 4];
+        mercator.meters_to_lat_lon_batch(&meters, &mut lat_lon);
+
+        for i in 0..coords.len() {
+            let (lat, lon) = coords[i];
+            assert_eq!(meters[i], mercator.lat_lon_to_meters(lat, lon));
+            assert_eq!(lat_lon[i], mercator.meters_to_lat_lon(meters[i].0, meters[i].1));
+        }
+    }
+
+    #[test]
+    #[ignore = "timing test, run with --ignored"]
+    fn test_batch_million_points() {
+        let mercator = GlobalMercator::default();
+        let coords = million_coords();
+        let mut meters = vec![(0.0, 0.0); coords.len()];
+
+        let start = std::time::Instant::now();
+        mercator.lat_lon_to_meters_batch(&coords, &mut meters);
+        let elapsed = start.elapsed();
+
+        for i in (0..coords.len()).step_by(9973) {
+            assert_eq!(meters[i], mercator.lat_lon_to_meters(coords[i].0, coords[i].1));
+        }
+        assert!(elapsed.as_secs() < 10, "batch took {:?}", elapsed);
+    }
+
+    #[test]
+    fn test_meters_pixels() {
+        let mercator = GlobalMercator::default();
+        let (mx, my) = (31100.00, 42200.1);
+        let zoom = 8;
+
+        let (px, py) = mercator.meters_to_pixels(mx, my, zoom);
+        let (mx_new, my_new) = mercator.pixels_to_meters(px, py, zoom);
+
+        assert!(approx_eq_meters(mx, mx_new), "failed to compare: {} != {}, (mx - mx_new).abs() = {}", mx, mx_new, (mx - mx_new).abs());
+        assert!(approx_eq_meters(my, my_new), "failed to compare: {} != {}, (my - my_new).abs() = {}", my, my_new, (my - my_new).abs());
+    }
+
+    #[test]
+    fn test_fractional_zoom_pixels() {
+        let mercator = GlobalMercator::default();
+        let (mx, my) = mercator.lat_lon_to_meters(48.6, 2.3);
+
+        for zoom in 0..20 {
+            let (px, py) = mercator.meters_to_pixels(mx, my, zoom);
+            let (fx, fy) = mercator.meters_to_pixels_f(mx, my, zoom as f64);
+            assert!((px - fx).abs() < 1e-6 && (py - fy).abs() < 1e-6, "zoom {}: {}, {} != {}, {}", zoom, px, py, fx, fy);
+        }
+
+        let (px8, py8) = mercator.meters_to_pixels(mx, my, 8);
+        let (px9, py9) = mercator.meters_to_pixels(mx, my, 9);
+        let (px, py) = mercator.meters_to_pixels_f(mx, my, 8.5);
+        assert!(px8 < px && px < px9 && py8 < py && py < py9, "{}, {}", px, py);
+
+        let (mx_new, my_new) = mercator.pixels_to_meters_f(px, py, 8.5);
+        assert!(approx_eq_meters(mx, mx_new) && approx_eq_meters(my, my_new), "{}, {}", mx_new, my_new);
+        assert_eq!(mercator.pixels_to_meters_f(px8, py8, 8.0), mercator.pixels_to_meters(px8, py8, 8));
+    }
+
+    #[test]
+    fn test_lat_lon_pixels() {
+        let mercator = GlobalMercator::default();
+        let (lat, lon) = (48.6, 2.3);
+        let zoom = 10;
+
+        let (px, py) = mercator.lat_lon_to_pixels(lat, lon, zoom);
+        let (lat_new, lon_new) = mercator.pixels_to_lat_lon(px, py, zoom);
+
+        assert!(approx_eq_deg(lat, lat_new), "failed to compare: {} != {}", lat, lat_new);
+        assert!(approx_eq_deg(lon, lon_new), "failed to compare: {} != {}", lon, lon_new);
+
+        // Bottom-left origin: the northern hemisphere is in the upper half of the map
+        assert!(py > mercator.map_size(zoom) as f64 / 2.0);
+        assert_eq!(mercator.pixels_to_lat_lon(0.0, 0.0, 0), mercator.meters_to_lat_lon(-mercator.origin_shift, -mercator.origin_shift));
+    }
+
+    #[test]
+    fn test_pixels_to_raster() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.pixels_to_raster(10.0, 56.0, 0), (10.0, 200.0));
+        assert_eq!(mercator.pixels_to_raster(10.0, 0.0, 1), (10.0, 512.0));
+
+        // 256 << 24 overflows a u32
+        for &zoom in &[24, 28] {
+            let map_size = 256.0 * (1u64 << zoom) as f64;
+            assert_eq!(mercator.pixels_to_raster(0.0, 0.0, zoom), (0.0, map_size));
+            assert_eq!(mercator.pixels_to_raster(0.0, 1.0, zoom), (0.0, map_size - 1.0));
+        }
+    }
+
+    #[test]
+    fn test_pixels_to_tile_exact() {
+        let mercator = GlobalMercator::default();
+
+        // Whichever side of the edge the rounding error falls, the exact edge's tile is picked
+        let edge = 134.0 * 256.0;
+        assert_eq!(mercator.pixels_to_tile(edge + 1e-9, edge), (134, 133));
+        for &offset in &[-1e-9, 0.0, 1e-9] {
+            assert_eq!(mercator.pixels_to_tile_exact(edge + offset, edge - offset), (133, 133), "{}", offset);
+        }
+        assert_eq!(mercator.pixels_to_tile_exact(edge + 0.5, edge - 0.5), mercator.pixels_to_tile(edge + 0.5, edge - 0.5));
+
+        for &(tx, ty, zoom) in &[(134, 90, 8), (1000, 17, 12), (12345, 54321, 17), (1 << 24, 3, 25)] {
+            let (minx, miny, _, _) = mercator.tile_bounds(tx, ty, zoom);
+            let (px, py) = mercator.meters_to_pixels(minx, miny, zoom);
+            assert_eq!(mercator.pixels_to_tile_exact(px, py), (tx - 1, ty - 1), "{}, {} at zoom {}", px, py, zoom);
+        }
+    }
+
+    #[test]
+    fn test_pixels_to_raster_tile() {
+        let mercator = GlobalMercator::default();
+
+        for &(lat, lon, zoom) in &[(48.6, 2.3, 10), (-33.9, 151.2, 5), (60.0, -150.0, 17), (0.1, 0.1, 1)] {
+            let (px, py) = mercator.lat_lon_to_pixels(lat, lon, zoom);
+            let (tx, ty) = mercator.pixels_to_tile(px, py);
+            let (gx, gy) = mercator.google_tile(tx, ty, zoom);
+            assert_eq!(mercator.pixels_to_raster_tile(px, py, zoom), (gx as i32, gy as i32), "{}, {}", lat, lon);
+        }
+
+        assert_eq!(mercator.pixels_to_raster_tile(10.0, 500.0, 1), (0, 0));
+        assert_eq!(mercator.pixels_to_raster_tile(300.0, 10.0, 1), (1, 1));
+    }
+
+    #[test]
+    fn test_lat_lon_to_tile_pixel() {
+        let mercator = GlobalMercator::default();
+        let (lat, lon, zoom) = (48.6, 2.3, 10);
+
+        let ((tx, ty), (x, y)) = mercator.lat_lon_to_tile_pixel(lat, lon, zoom);
+        let (px, py) = mercator.lat_lon_to_pixels(lat, lon, zoom);
+        assert_eq!((tx, ty), mercator.lat_lon_to_tile(lat, lon, zoom));
+        assert!(0.0 < x && x <= 256.0 && 0.0 < y && y <= 256.0, "{}, {}", x, y);
+        assert_eq!((x, y), (px % 256.0, py % 256.0));
+
+        // On the edges between tiles the point is at the far side of the lower tile
+        let (lat, lon) = (0.0, 0.0);
+        let ((tx, ty), (x, y)) = mercator.lat_lon_to_tile_pixel(lat, lon, 1);
+        let (px, py) = mercator.lat_lon_to_pixels(lat, lon, 1);
+        for (local, global, tile) in [(x, px, tx), (y, py, ty)].iter() {
+            assert!(local.abs() < 1e-6 || (local - 256.0).abs() < 1e-6, "{}", local);
+            assert!((*tile as f64 * 256.0 + local - global).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_tile_raster_pixels() {
+        let mercator = GlobalMercator::default();
+        let zoom = 3;
+
+        // The top row of TMS tiles starts at raster y = 0
+        assert_eq!(mercator.tile_to_raster_pixels(0, 7, zoom), (0, 0));
+        assert_eq!(mercator.tile_to_raster_pixels(5, 7, zoom), (1280, 0));
+        assert_eq!(mercator.tile_to_raster_pixels(5, 0, zoom), (1280, 1792));
+
+        assert_eq!(mercator.raster_pixels_to_tile(0, 0, zoom), (0, 7));
+        assert_eq!(mercator.raster_pixels_to_tile(1280 + 255, 1792 + 255, zoom), (5, 0));
+        for &(tx, ty) in &[(0, 0), (3, 2), (7, 7)] {
+            let (px, py) = mercator.tile_to_raster_pixels(tx, ty, zoom);
+            assert_eq!(mercator.raster_pixels_to_tile(px, py, zoom), (tx, ty));
+        }
+
+        // No overflow at high zoom
+        assert_eq!(mercator.tile_to_raster_pixels(1 << 29, 0, 30), (1 << 37, ((1 << 30) - 1) * 256));
+    }
+
+    // TODO: pixels_to_tile is wrong, it should be using 'floor' not 'ceil' - 1 because of we are on the min edge the divide is exact so -1 puts us in the wrong tile
+//    #[test]
+//    fn test_pixels_tile() {
+//        let mercator = GlobalMercator::default();
+//        let (px, py) = (0.0, 0.0);
+//        let zoom = 8;
+//
+//        let (tx, ty) = mercator.pixels_to_tile(px, py);
+//        let tile_size = mercator.tile_size();
+//        let (px_new, py_new) = ((tx * tile_size as i32) as f64, (ty * tile_size as i32) as f64);
+//
+//        assert_eq!(px, px_new);
+//        assert_eq!(py, py_new);
+//    }
+
+    #[test]
+    fn test_parent_tile() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.parent_tile(0, 0, 0), None);
+        assert_eq!(mercator.parent_tile(1, 1, 1), Some((0, 0, 0)));
+        assert_eq!(mercator.parent_tile(135, 90, 8), Some((67, 45, 7)));
+
+        for &(tx, ty, zoom) in &mercator.child_tiles(67, 45, 7) {
+            assert_eq!(mercator.parent_tile(tx, ty, zoom), Some((67, 45, 7)));
+        }
+    }
+
+    #[test]
+    fn test_child_tiles() {
+        let mercator = GlobalMercator::default();
+        let (tx, ty, zoom) = (67, 45, 7);
+        let parent = mercator.tile_bounds_struct(tx, ty, zoom);
+
+        let children = mercator.child_tiles(tx, ty, zoom);
+        assert_eq!(children, [(134, 90, 8), (135, 90, 8), (134, 91, 8), (135, 91, 8)]);
+        for &(cx, cy, cz) in &children {
+            let child = mercator.tile_bounds_struct(cx, cy, cz);
+            assert!(parent.contains(child.min_x, child.min_y), "{:?} not in {:?}", child, parent);
+            assert!(parent.contains(child.max_x, child.max_y), "{:?} not in {:?}", child, parent);
+        }
+
+        // The same relationship holds in Google coordinates
+        let (gx, gy) = mercator.google_tile(tx, ty, zoom);
+        for &(cx, cy, cz) in &mercator.child_tiles(gx as i32, gy as i32, zoom) {
+            let (tx_child, ty_child) = mercator.google_to_tms(cx, cy, cz);
+            assert!(children.contains(&(tx_child, ty_child, cz)));
+        }
+    }
+
+    #[test]
+    fn test_child_tiles_max_zoom() {
+        let mercator = GlobalMercator::default();
+        let last = (1 << 30) - 1;
+
+        let children = mercator.child_tiles(last, last, 30);
+        assert_eq!(children[3], (i32::MAX, i32::MAX, 31));
+        assert!(children.iter().all(|&(cx, cy, cz)| mercator.is_valid_tile(cx, cy, cz)));
+    }
+
+    #[test]
+    #[should_panic(expected = "have no children")]
+    fn test_child_tiles_past_max_zoom() {
+        GlobalMercator::default().child_tiles(1 << 30, 0, 31);
+    }
+
+    #[test]
+    #[should_panic(expected = "has no children")]
+    fn test_child_tiles_overflow() {
+        GlobalMercator::default().child_tiles(i32::MAX, 0, 30);
+    }
+
+    #[test]
+    fn test_clamp_tile() {
+        let mercator = GlobalMercator::default();
+
+        assert!(mercator.is_valid_tile(0, 0, 0));
+        assert!(mercator.is_valid_tile(7, 7, 3));
+        assert!(!mercator.is_valid_tile(-1, 0, 3));
+        assert!(!mercator.is_valid_tile(0, 8, 3));
+        assert!(mercator.is_valid_tile(i32::MAX, 0, 31));
+
+        assert_eq!(mercator.clamp_tile(-3, 2, 3), (0, 2));
+        assert_eq!(mercator.clamp_tile(4, 12, 3), (4, 7));
+        assert_eq!(mercator.clamp_tile(5, -1, 0), (0, 0));
+        assert_eq!(mercator.clamp_tile(5, 6, 3), (5, 6));
+        assert_eq!(mercator.clamp_tile(i32::MAX, 0, 40), (i32::MAX, 0));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_parents_covering() {
+        let mercator = GlobalMercator::default();
+
+        let children: Vec<_> = mercator.child_tiles(6, 9, 4).iter().map(|&(tx, ty, _)| (tx, ty)).collect();
+        assert_eq!(mercator.parents_covering(&children, 5, 4), [(6, 9)]);
+
+        let mixed = [(12, 18), (13, 19), (0, 0), (31, 31), (1, 1), (12, 19)];
+        assert_eq!(mercator.parents_covering(&mixed, 5, 4), [(6, 9), (0, 0), (15, 15)]);
+        assert_eq!(mercator.parents_covering(&mixed, 5, 2), [(1, 2), (0, 0), (3, 3)]);
+        assert_eq!(mercator.parents_covering(&mixed, 5, 0), [(0, 0)]);
+        assert_eq!(mercator.parents_covering(&mixed[..2], 5, 5), [(12, 18), (13, 19)]);
+    }
+
+    #[test]
+    fn test_wmts_rows() {
+        let mercator = GlobalMercator::default();
+
+        for &(tx, ty, zoom) in &[(0, 0, 0), (1, 0, 1), (3, 2, 3), (5, 9, 4), (1000, 17, 12)] {
+            let (gx, gy) = mercator.google_tile(tx, ty, zoom);
+            assert_eq!(mercator.tms_to_wmts(tx, ty, zoom), Some((gx as u32, gy as u32)));
+            assert_eq!(mercator.wmts_to_tms(gx as u32, gy as u32, zoom), Some((tx, ty)));
+        }
+
+        assert_eq!(mercator.tms_to_wmts(-1, 0, 3), None);
+        assert_eq!(mercator.tms_to_wmts(0, 8, 3), None);
+        assert_eq!(mercator.wmts_to_tms(8, 0, 3), None);
+        assert_eq!(mercator.wmts_to_tms(0, 1, 0), None);
+    }
+
+    #[test]
+    fn test_high_zoom_indices() {
+        let mercator = GlobalMercator::default();
+        let (mx, my) = mercator.lat_lon_to_meters(48.6, 2.3);
+
+        let (px, py) = mercator.meters_to_pixels(mx, my, 30);
+        let (tx, ty) = mercator.pixels_to_tile(px, py);
+        assert_eq!(mercator.pixels_to_tile_i64(px, py), (tx as i64, ty as i64));
+        assert_eq!(mercator.google_tile(tx, ty, 30), (tx as i64, (1 << 30) - 1 - ty as i64));
+        let (minx, miny, maxx, maxy) = mercator.tile_bounds(tx, ty, 30);
+        assert!(minx <= mx && mx <= maxx && miny <= my && my <= maxy);
+
+        // Past zoom 31 the i32 indices saturate, the i64 ones keep going
+        let (px, py) = mercator.meters_to_pixels(mx, my, 32);
+        let (tx, ty) = mercator.pixels_to_tile_i64(px, py);
+        assert!(tx > i32::MAX as i64 && ty > i32::MAX as i64, "{}, {}", tx, ty);
+        assert!(tx < 1 << 32 && ty < 1 << 32);
+        assert_eq!(mercator.pixels_to_tile(px, py), (i32::MAX, i32::MAX));
+        assert_eq!(mercator.google_tile(0, 10, 32), (0, (1 << 32) - 11));
+    }
+
+    #[test]
+    fn test_google_to_tms() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.google_tile(0, 0, 0), (0, 0));
+        assert_eq!(mercator.google_to_tms(0, 0, 0), (0, 0));
+
+        for &(tx, ty, zoom) in &[(0, 0, 1), (1, 0, 1), (3, 2, 3), (5, 9, 4), (1000, 17, 12)] {
+            let (gx, gy) = mercator.google_tile(tx, ty, zoom);
+            assert_eq!(mercator.google_to_tms(gx as i32, gy as i32, zoom), (tx, ty));
+        }
+
+        // Out of range rows are mirrored rather than rejected
+        assert_eq!(mercator.google_to_tms(0, -1, 3), (0, 8));
+    }
+
+    #[test]
+    fn test_flip_tile_y() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.flip_tile_y(0, 0), 0);
+        assert_eq!(mercator.flip_tile_y(0, 3), 7);
+        assert_eq!(mercator.flip_tile_y(2, 3), 5);
+        assert_eq!(mercator.flip_tile_y(0, 31), i32::MAX);
+
+        for &(tx, ty, zoom) in &[(0, 0, 1), (3, 2, 3), (1000, 17, 12), (0, 1 << 29, 30), (5, i32::MAX, 31)] {
+            assert_eq!(mercator.flip_tile_y(mercator.flip_tile_y(ty, zoom), zoom), ty);
+            assert_eq!(mercator.google_tile(tx, ty, zoom).1, mercator.flip_tile_y(ty, zoom) as i64);
+        }
+    }
+
+    #[test]
+    fn test_row_flips_past_zoom_63() {
+        let mercator = GlobalMercator::default();
+        assert_eq!(mercator.num_tiles(63), 1 << 63);
+        assert_eq!(mercator.num_tiles(64), u64::MAX);
+        assert_eq!(mercator.num_tiles(100), u64::MAX);
+
+        // Saturated instead of overflowing the shift
+        for &zoom in &[62, 63, 64, 100] {
+            assert_eq!(mercator.google_tile(5, 0, zoom), (5, mercator.grid_len(zoom) - 1));
+            assert_eq!(mercator.flip_tile_y(0, zoom), i32::MAX);
+            assert_eq!(mercator.neighbor(5, 5, zoom, Direction::East, true), Some((6, 5)));
+            assert_eq!(mercator.offset_tile(5, 5, zoom, -2, 3, false), Some((3, 8)));
+            let (tx, _) = mercator.meters_to_tile_wrapped(0.0, 0.0, zoom);
+            assert_eq!(tx, mercator.meters_to_tile(0.0, 0.0, zoom).0);
+        }
+    }
+
+    #[test]
+    fn test_meters_to_tile_wrapped() {
+        let mercator = GlobalMercator::default();
+        let zoom = 5;
+        let n = mercator.num_tiles(zoom) as i32;
+
+        // A track crossing the antimeridian eastwards, with the second point left unnormalized
+        let (west_x, west_y) = mercator.lat_lon_to_meters(10.0, 179.9);
+        let (east_x, east_y) = mercator.lat_lon_to_meters(10.0, 180.1);
+
+        let (west, _) = mercator.meters_to_tile(west_x, west_y, zoom);
+        let (east, _) = mercator.meters_to_tile(east_x, east_y, zoom);
+        assert_eq!((west, east), (n - 1, n));
+
+        let (west, _) = mercator.meters_to_tile_wrapped(west_x, west_y, zoom);
+        let (east, _) = mercator.meters_to_tile_wrapped(east_x, east_y, zoom);
+        assert_eq!((west, east), (n - 1, 0));
+        assert_eq!((east - west).rem_euclid(n), 1);
+
+        // The normalized point on the other side lands on the same column
+        let (x, y) = mercator.lat_lon_to_meters(10.0, -179.9);
+        assert_eq!(mercator.meters_to_tile_wrapped(x, y, zoom).0, east);
+
+        // Rows are left alone
+        let (x, y) = mercator.lat_lon_to_meters(89.0, 0.0);
+        assert_eq!(mercator.meters_to_tile_wrapped(x, y, zoom), mercator.meters_to_tile(x, y, zoom));
+    }
+
+    #[test]
+    fn test_meters_to_tile_snapped() {
+        let mercator = GlobalMercator::default();
+        let (mx, my) = mercator.lat_lon_to_meters(0.0, 0.0);
+
+        assert_eq!(mercator.meters_to_tile(mx, my, 12), (2047, 2047));
+        assert_eq!(mercator.meters_to_tile_snapped(mx, my, 12, 1e-6), (2048, 2048));
+        assert_eq!(mercator.meters_to_tile_snapped(mx, my, 0, 1e-6), (0, 0));
+
+        // Away from the axes nothing changes
+        let (mx, my) = mercator.lat_lon_to_meters(-33.9, 151.2);
+        assert_eq!(mercator.meters_to_tile_snapped(mx, my, 12, 1e-6), mercator.meters_to_tile(mx, my, 12));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_origin_quad_tree() {
+        let mercator = GlobalMercator::default();
+        let (mx, my) = mercator.lat_lon_to_meters(0.0, 0.0);
+
+        let (tx, ty) = mercator.meters_to_tile(mx, my, 12);
+        assert_eq!(mercator.quad_tree(tx, ty, 12), "211111111111");
+
+        // The origin is the corner of four tiles, snapping picks the one to its north-east
+        let (tx, ty) = mercator.meters_to_tile_snapped(mx, my, 12, 1e-6);
+        assert_eq!(mercator.quad_tree(tx, ty, 12), "122222222222");
+    }
+
+    #[test]
+    fn test_try_meters_to_tile() {
+        let mercator = GlobalMercator::default();
+        let edge = mercator.origin_shift;
+        let zoom = 4;
+
+        assert_eq!(mercator.try_meters_to_tile(edge - 1.0, 0.0, zoom), Ok(mercator.meters_to_tile(edge - 1.0, 0.0, zoom)));
+        assert_eq!(mercator.try_meters_to_tile(edge, 0.0, zoom), Ok((15, 7)));
+        assert_eq!(mercator.try_meters_to_tile(edge + 1.0, 0.0, zoom), Err(GlobalMercatorError::OutOfExtent { axis: Axis::X, value: edge + 1.0 }));
+        assert_eq!(mercator.try_meters_to_tile(0.0, -edge - 1.0, zoom), Err(GlobalMercatorError::OutOfExtent { axis: Axis::Y, value: -edge - 1.0 }));
+    }
+
+    #[test]
+    fn test_lat_lon_to_tile() {
+        let mercator = GlobalMercator::default();
+        let (lat, lon) = (51.5074, -0.1278); // London
+
+        let (mx, my) = mercator.lat_lon_to_meters(lat, lon);
+        assert_eq!(mercator.lat_lon_to_tile(lat, lon, 10), mercator.meters_to_tile(mx, my, 10));
+        assert_eq!(mercator.lat_lon_to_tile(lat, lon, 10), (511, 683));
+        assert_eq!(mercator.lat_lon_to_google_tile(lat, lon, 10), (511, 340));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_lat_lon_to_tile_quad_tree() {
+        let mercator = GlobalMercator::default();
+
+        let (tx, ty, zoom) = mercator.quad_tree_to_tile("120220011203").unwrap();
+        let (lat, lon) = mercator.tile_center_lat_lon(tx, ty, zoom);
+        assert_eq!(mercator.lat_lon_to_tile(lat, lon, zoom), (tx, ty));
+
+        let (tx, ty) = mercator.lat_lon_to_tile(lat, lon, zoom);
+        assert_eq!(mercator.quad_tree(tx, ty, zoom), "120220011203");
+    }
+
+    #[test]
+    fn test_tiles_for_bbox() {
+        let mercator = GlobalMercator::default();
+        let zoom = 4;
+        let (min_lat, min_lon, max_lat, max_lon) = (40.0, -10.0, 60.0, 30.0);
+
+        let tiles: Vec<_> = mercator.tiles_for_bbox(min_lat, min_lon, max_lat, max_lon, zoom).collect();
+        let (minx, miny) = mercator.lat_lon_to_meters(min_lat, min_lon);
+        let (maxx, maxy) = mercator.lat_lon_to_meters(max_lat, max_lon);
+        let (tx_min, ty_min) = mercator.meters_to_tile(minx, miny, zoom);
+        let (tx_max, ty_max) = mercator.meters_to_tile(maxx, maxy, zoom);
+
+        assert_eq!(tiles.len() as i32, (tx_max - tx_min + 1) * (ty_max - ty_min + 1));
+        assert!(tiles.contains(&(tx_min, ty_min)));
+        assert!(tiles.contains(&(tx_max, ty_max)));
+
+        // Corner order does not matter
+        let swapped: Vec<_> = mercator.tiles_for_bbox(max_lat, max_lon, min_lat, min_lon, zoom).collect();
+        assert_eq!(tiles, swapped);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_tile_aligned_chunks() {
+        let mercator = GlobalMercator::default();
+        let zoom = 6;
+
+        // Centers of the corner tiles of a 5x5 tile region
+        let (min_lat, min_lon) = mercator.tile_center_lat_lon(10, 20, zoom);
+        let (max_lat, max_lon) = mercator.tile_center_lat_lon(14, 24, zoom);
+        let chunks = mercator.tile_aligned_chunks(min_lat, min_lon, max_lat, max_lon, zoom, 2);
+
+        assert_eq!(chunks.len(), 9);
+        assert_eq!(chunks[0], (10, 20, 11, 21));
+        assert_eq!(chunks[2], (10, 24, 11, 24));
+        assert_eq!(chunks[8], (14, 24, 14, 24));
+        for &(tx_min, ty_min, tx_max, ty_max) in &chunks {
+            let (width, height) = (tx_max - tx_min + 1, ty_max - ty_min + 1);
+            assert_eq!(width, if tx_min == 14 { 1 } else { 2 });
+            assert_eq!(height, if ty_min == 24 { 1 } else { 2 });
+        }
+
+        // Together the chunks cover every tile exactly once
+        let covered: usize = chunks.iter().map(|&(x0, y0, x1, y1)| ((x1 - x0 + 1) * (y1 - y0 + 1)) as usize).sum();
+        assert_eq!(covered, mercator.tiles_for_bbox(min_lat, min_lon, max_lat, max_lon, zoom).count());
+
+        let whole = mercator.tile_aligned_chunks(min_lat, min_lon, max_lat, max_lon, zoom, 8);
+        assert_eq!(whole, vec![(10, 20, 14, 24)]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_split_antimeridian() {
+        let mercator = GlobalMercator::default();
+
+        let boxes = mercator.split_antimeridian(-20.0, 170.0, 10.0, -170.0);
+        assert_eq!(boxes, vec![(-20.0, 170.0, 10.0, 180.0), (-20.0, -180.0, 10.0, -170.0)]);
+        assert_eq!(mercator.split_antimeridian(-20.0, -170.0, 10.0, 170.0), vec![(-20.0, -170.0, 10.0, 170.0)]);
+
+        // At zoom 3 each half is one column wide, at either side of the grid
+        let columns: Vec<i32> = boxes.iter().flat_map(|&(a, b, c, d)| mercator.tiles_for_bbox(a, b, c, d, 3)).map(|(tx, _)| tx).collect();
+        assert!(columns.iter().all(|&tx| tx == 0 || tx == 7), "{:?}", columns);
+        assert!(columns.contains(&0) && columns.contains(&7));
+    }
+
+    #[test]
+    fn test_tiles_for_bbox_clamped() {
+        let mercator = GlobalMercator::default();
+
+        let tiles: Vec<_> = mercator.tiles_for_bbox(-90.0, -180.0, 90.0, 180.0, 2).collect();
+        assert_eq!(tiles.len(), 16);
+        assert!(tiles.iter().all(|&(tx, ty)| (0..4).contains(&tx) && (0..4).contains(&ty)));
+    }
+
+    #[test]
+    fn test_tile_bbox_pixel_window() {
+        let mercator = GlobalMercator::default();
+
+        // The south-west zoom 1 tile covers -180..0 and -85..0
+        assert_eq!(mercator.tile_bbox_pixel_window(0, 0, 1, Bounds::new(-190.0, -89.0, 10.0, 10.0)), Some((0, 0, 256, 256)));
+        assert_eq!(mercator.tile_bbox_pixel_window(0, 0, 1, mercator.tile_lat_lon_bounds_struct(0, 0, 1)), Some((0, 0, 256, 256)));
+
+        // Western half of the tile, then its northern edge
+        assert_eq!(mercator.tile_bbox_pixel_window(0, 0, 1, Bounds::new(-190.0, -89.0, -90.1, 10.0)), Some((0, 0, 128, 256)));
+        let (_, y_off, _, height) = mercator.tile_bbox_pixel_window(0, 0, 1, Bounds::new(-190.0, -10.0, 10.0, 10.0)).unwrap();
+        assert_eq!(y_off, 0);
+        assert!(0 < height && height < 32, "{}", height);
+
+        // Southern edge, with the window ending at the bottom of the tile
+        let (x_off, y_off, width, height) = mercator.tile_bbox_pixel_window(0, 0, 1, Bounds::new(-100.0, -89.0, -80.0, -70.0)).unwrap();
+        assert_eq!(y_off + height, 256);
+        assert!(x_off < 128 && 128 < x_off + width);
+
+        assert_eq!(mercator.tile_bbox_pixel_window(0, 0, 1, Bounds::new(10.0, 10.0, 20.0, 20.0)), None);
+        assert_eq!(mercator.tile_bbox_pixel_window(1, 1, 1, Bounds::new(10.0, 10.0, 20.0, 20.0)).map(|window| window.0 > 0), Some(true));
+    }
+
+    #[test]
+    fn test_haversine_distance() {
+        let mercator = GlobalMercator::default();
+
+        // A degree of latitude on the equator
+        let degree = mercator.haversine_distance(0.0, 0.0, 1.0, 0.0);
+        assert!((degree - 111_319.49).abs() < 0.01, "{}", degree);
+        assert_eq!(mercator.haversine_distance(0.0, 0.0, 0.0, 1.0), degree);
+        assert_eq!(mercator.haversine_distance(48.6, 2.3, 48.6, 2.3), 0.0);
+
+        // Meridians converge, at 60° a degree of longitude is half as long
+        let north = mercator.haversine_distance(60.0, 0.0, 60.0, 1.0);
+        assert!((north / degree - 0.5).abs() < 1e-4, "{}", north / degree);
+
+        // London to Paris is about 344km
+        let distance = mercator.haversine_distance(51.5074, -0.1278, 48.8566, 2.3522);
+        assert!((distance - 344_000.0).abs() < 2_000.0, "{}", distance);
+
+        let half_circumference = mercator.haversine_distance(0.0, -90.0, 0.0, 90.0);
+        assert!((half_circumference - PI * WGS84_RADIUS).abs() < 1e-6, "{}", half_circumference);
+    }
+
+    #[test]
+    fn test_tile_diagonal_meters() {
+        let mercator = GlobalMercator::default();
+        let (lat, lon) = (51.5074, -0.1278);
+
+        // Each zoom halves the diagonal, roughly: at low zooms a tile spans latitudes of quite
+        // different scales
+        for zoom in 10..24 {
+            let (tx, ty) = mercator.lat_lon_to_tile(lat, lon, zoom);
+            let (cx, cy) = mercator.lat_lon_to_tile(lat, lon, zoom + 1);
+            let ratio = mercator.tile_diagonal_meters(tx, ty, zoom) / mercator.tile_diagonal_meters(cx, cy, zoom + 1);
+            assert!((ratio - 2.0).abs() < 0.02, "zoom {}: {}", zoom, ratio);
+        }
+
+        // Near the equator a zoom 10 tile is about 39km wide, so 55km across
+        let (tx, ty) = mercator.lat_lon_to_tile(0.1, 0.1, 10);
+        let diagonal = mercator.tile_diagonal_meters(tx, ty, 10);
+        let expected = mercator.resolution(10) * 256.0 * 2f64.sqrt();
+        assert!((diagonal / expected - 1.0).abs() < 1e-3, "{} != {}", diagonal, expected);
+    }
+
+    #[test]
+    fn test_tile_ground_area() {
+        let mercator = GlobalMercator::default();
+
+        let equatorial = mercator.tile_ground_area(5, 7, 4);
+        let polar = mercator.tile_ground_area(5, 15, 4);
+        assert!(equatorial > 10.0 * polar, "{} vs {}", equatorial, polar);
+        assert_eq!(mercator.tile_ground_area(5, 8, 4), equatorial);
+
+        // The four zoom 1 tiles cover the sphere up to the Mercator cutoff
+        let total: f64 = (0..2).flat_map(|tx| (0..2).map(move |ty| (tx, ty))).map(|(tx, ty)| mercator.tile_ground_area(tx, ty, 1)).sum();
+        let sphere = 4.0 * PI * WGS84_RADIUS * WGS84_RADIUS;
+        let expected = sphere * MAX_LATITUDE.to_radians().sin();
+        assert!((total - expected).abs() / expected < 1e-6, "{} != {}", total, expected);
+        assert!(total < sphere);
+    }
+
+    #[test]
+    fn test_reproject_tile() {
+        let large = GlobalMercator::new(512);
+        let standard = GlobalMercator::new(256);
+        let (tx, ty, zoom) = (67, 45, 7);
+
+        assert_eq!(large.reproject_tile(tx, ty, zoom, &standard), (tx, ty));
+        assert_eq!(standard.reproject_tile(tx, ty, zoom, &large), (tx, ty));
+
+        // The 256 pixel tiles with the resolution of the 512 pixel tile are one zoom deeper
+        let (minx, miny, maxx, maxy) = large.tile_bounds(tx, ty, zoom);
+        let [(x0, y0, z), _, _, (x1, y1, _)] = standard.child_tiles(tx, ty, zoom);
+        assert_eq!(large.resolution(zoom), standard.resolution(z));
+        let (child_minx, child_miny, _, _) = standard.tile_bounds(x0, y0, z);
+        let (_, _, child_maxx, child_maxy) = standard.tile_bounds(x1, y1, z);
+        assert_eq!((child_minx, child_miny, child_maxx, child_maxy), (minx, miny, maxx, maxy));
+
+        let xyz = GlobalMercator::new(256).with_origin(Origin::TopLeft);
+        let (gx, gy) = large.google_tile(tx, ty, zoom);
+        assert_eq!(large.reproject_tile(tx, ty, zoom, &xyz), (gx as i32, gy as i32));
+    }
+
+    #[test]
+    fn test_snap_to_tile_grid() {
+        let mercator = GlobalMercator::default();
+        let (lat, lon, zoom) = (48.6, 2.3, 10);
+
+        let (tx, ty) = mercator.lat_lon_to_tile(lat, lon, zoom);
+        let (min_lat, min_lon, max_lat, max_lon) = mercator.tile_lat_lon_bounds(tx, ty, zoom);
+        let (snapped_lat, snapped_lon) = mercator.snap_to_tile_grid(lat, lon, zoom, CornerSnap::NorthWest);
+        assert!(approx_eq_deg(snapped_lat, max_lat) && approx_eq_deg(snapped_lon, min_lon), "{}, {}", snapped_lat, snapped_lon);
+
+        let (nearest_lat, nearest_lon) = mercator.snap_to_tile_grid(lat, lon, zoom, CornerSnap::Nearest);
+        assert!(approx_eq_deg(nearest_lat, min_lat) || approx_eq_deg(nearest_lat, max_lat), "{}", nearest_lat);
+        assert!(approx_eq_deg(nearest_lon, min_lon) || approx_eq_deg(nearest_lon, max_lon), "{}", nearest_lon);
+
+        // Corners stay put
+        for &snap in &[CornerSnap::NorthWest, CornerSnap::Nearest] {
+            for &(lat, lon) in &[(min_lat, min_lon), (max_lat, min_lon), (min_lat, max_lon), (max_lat, max_lon), (snapped_lat, snapped_lon)] {
+                let (again_lat, again_lon) = mercator.snap_to_tile_grid(lat, lon, zoom, snap);
+                assert!(approx_eq_deg(again_lat, lat) && approx_eq_deg(again_lon, lon), "{:?}: {}, {} moved to {}, {}", snap, lat, lon, again_lat, again_lon);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tile_corners() {
+        let mercator = GlobalMercator::default();
+        let (tx, ty, zoom) = (134, 90, 8);
+
+        let (minx, _, _, maxy) = mercator.tile_bounds(tx, ty, zoom);
+        let (_, min_lon, max_lat, _) = mercator.tile_lat_lon_bounds(tx, ty, zoom);
+        let meters = mercator.tile_corners_meters(tx, ty, zoom);
+        let lat_lon = mercator.tile_corners_lat_lon(tx, ty, zoom);
+        assert_eq!(meters[0], (minx, maxy));
+        assert_eq!(lat_lon[0], (max_lat, min_lon));
+
+        // A simple counter-clockwise ring turns left at every corner
+        let points: Vec<(f64, f64)> = lat_lon.iter().map(|&(lat, lon)| (lon, lat)).collect();
+        for ring in [&meters[..], &points[..]].iter() {
+            for i in 0..4 {
+                let (a, b, c) = (ring[i], ring[(i + 1) % 4], ring[(i + 2) % 4]);
+                let cross = (b.0 - a.0) * (c.1 - b.1) - (b.1 - a.1) * (c.0 - b.0);
+                assert!(cross > 0.0, "corner {} turns right: {:?}", i, ring);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_tile_densified_ring() {
+        let mercator = GlobalMercator::default();
+        let (tx, ty, zoom) = (2, 3, 2);
+
+        let ring = mercator.tile_densified_ring(tx, ty, zoom, 8);
+        assert_eq!(ring.len(), 4 * 8 + 1);
+        assert_eq!(ring.first(), ring.last());
+
+        let corners = mercator.tile_corners_lat_lon(tx, ty, zoom);
+        for (i, corner) in corners.iter().enumerate() {
+            let (lat, lon) = ring[i * 8];
+            assert!(approx_eq_deg(lat, corner.0) && approx_eq_deg(lon, corner.1), "{:?} != {:?}", ring[i * 8], corner);
+        }
+
+        // Points along the west edge are evenly spaced in meters, so not in degrees
+        let (_, miny, _, maxy) = mercator.tile_bounds(tx, ty, zoom);
+        for (step, &(lat, lon)) in ring[..8].iter().enumerate() {
+            let (_, my) = mercator.lat_lon_to_meters(lat, lon);
+            assert!(approx_eq_meters(my, maxy - (maxy - miny) * step as f64 / 8.0), "{}: {}", step, my);
+            assert!(approx_eq_deg(lon, corners[0].1));
+        }
+        let (north, middle, south) = (ring[0].0, ring[4].0, ring[8].0);
+        // Mercator stretches the north, so the northern half of the edge spans fewer degrees
+        assert!(north - middle < middle - south, "{} {} {}", north, middle, south);
+
+        assert_eq!(mercator.tile_densified_ring(tx, ty, zoom, 0).len(), 5);
+        assert_eq!(mercator.tile_densified_ring(tx, ty, zoom, 1), mercator.tile_densified_ring(tx, ty, zoom, 0));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_tile_pixel_grid_lat_lon() {
+        let mercator = GlobalMercator::new(16);
+        let (tx, ty, zoom) = (5, 9, 4);
+
+        let grid = mercator.tile_pixel_grid_lat_lon(tx, ty, zoom);
+        assert_eq!(grid.len(), 16 * 16);
+        assert!(grid.iter().copied().eq(mercator.tile_pixel_grid_lat_lon_iter(tx, ty, zoom)));
+
+        // The first pixel center is half a pixel south-east of the north-west corner
+        let (minx, _, _, maxy) = mercator.tile_bounds(tx, ty, zoom);
+        let half = mercator.resolution(zoom) / 2.0;
+        let (lat, lon) = mercator.meters_to_lat_lon(minx + half, maxy - half);
+        assert!(approx_eq_deg(grid[0].0, lat) && approx_eq_deg(grid[0].1, lon), "{:?} != {:?}", grid[0], (lat, lon));
+
+        // Row-major: the rest of the first row shares its latitude, the next row is further south
+        assert!(grid[1..16].iter().all(|&(row_lat, _)| row_lat == grid[0].0));
+        assert!(grid[1].1 > grid[0].1 && grid[16].0 < grid[0].0);
+        assert_eq!(grid[16].1, grid[0].1);
+
+        // Every center is within the tile
+        let (min_lat, min_lon, max_lat, max_lon) = mercator.tile_lat_lon_bounds(tx, ty, zoom);
+        for &(lat, lon) in &grid {
+            assert!(min_lat < lat && lat < max_lat && min_lon < lon && lon < max_lon, "{} {}", lat, lon);
+        }
+        assert_eq!(GlobalMercator::default().tile_pixel_grid_lat_lon(0, 0, 1).len(), 65536);
+    }
+
+    #[test]
+    fn test_tile_center() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.tile_center_meters(0, 0, 0), (0.0, 0.0));
+        assert_eq!(mercator.tile_center_lat_lon(0, 0, 0), (0.0, 0.0));
+
+        let (tx, ty, zoom) = (10, 14, 4);
+        let (minx, miny, maxx, maxy) = mercator.tile_bounds(tx, ty, zoom);
+        let (cx, cy) = mercator.tile_center_meters(tx, ty, zoom);
+        assert!(minx < cx && cx < maxx && miny < cy && cy < maxy);
+
+        // Near the pole the averaged latitude is noticeably further south than the real center
+        let (min_lat, min_lon, max_lat, max_lon) = mercator.tile_lat_lon_bounds(tx, ty, zoom);
+        let (lat, lon) = mercator.tile_center_lat_lon(tx, ty, zoom);
+        assert!((lon - (min_lon + max_lon) / 2.0).abs() < 1e-9);
+        assert!(lat - (min_lat + max_lat) / 2.0 > 0.1, "{} vs {}", lat, (min_lat + max_lat) / 2.0);
+        assert_eq!(mercator.meters_to_tile(cx, cy, zoom), (tx, ty));
+    }
+
+    #[test]
+    fn test_world_extent() {
+        let mercator = GlobalMercator::default();
+        let shift = mercator.origin_shift;
+
+        assert_eq!(mercator.world_extent_meters(), (-shift, -shift, shift, shift));
+        assert_eq!(mercator.world_extent_meters(), mercator.tile_bounds(0, 0, 0));
+        assert!((shift - 20037508.34).abs() < 0.01, "{}", shift);
+
+        let (min_lat, min_lon, max_lat, max_lon) = mercator.world_extent_lat_lon();
+        assert_eq!((min_lon, max_lon), (-180.0, 180.0));
+        assert!(approx_eq_deg(max_lat, MAX_LATITUDE), "{} != {}", max_lat, MAX_LATITUDE);
+        assert!((max_lat - 85.0511).abs() < 1e-4, "{}", max_lat);
+        assert!(approx_eq_deg(min_lat, -max_lat), "{} != {}", min_lat, -max_lat);
+    }
+
+    #[test]
+    fn test_max_latitude() {
+        let mercator = GlobalMercator::default();
+        let max_lat = mercator.max_latitude();
+        assert!((max_lat - 85.0511).abs() < 1e-4, "{}", max_lat);
+        assert!(approx_eq_deg(max_lat, MAX_LATITUDE), "{} != {}", max_lat, MAX_LATITUDE);
+        let (_, my) = mercator.lat_lon_to_meters(max_lat, 0.0);
+        assert!(approx_eq_meters(my, mercator.origin_shift), "{} != {}", my, mercator.origin_shift);
+
+        // The cutoff only depends on the shape of the projection, not on its scale
+        for &(tile_size, radius) in &[(256, 6371008.8), (512, 6378137.0), (256, 1.0)] {
+            let other = GlobalMercator::with_radius(tile_size, radius);
+            assert!(approx_eq_deg(other.max_latitude(), max_lat), "{} != {}", other.max_latitude(), max_lat);
+            let (_, my) = other.lat_lon_to_meters(other.max_latitude(), 0.0);
+            assert!((my - other.origin_shift).abs() <= other.origin_shift * 1e-12, "{} != {}", my, other.origin_shift);
+        }
+    }
+
+    #[test]
+    fn test_viewport_pixel_to_lat_lon() {
+        let mercator = GlobalMercator::default();
+        let (lat, lon) = (51.5074, -0.1278);
+
+        for &zoom in &[0.0, 3.5, 12.0, 21.25] {
+            assert_eq!(mercator.viewport_pixel_to_lat_lon(lat, lon, zoom, 0.0, 0.0), (lat, lon));
+
+            // Tiny offsets go through the round trip, and land on the center up to rounding
+            for &(dx, dy) in &[(1e-9, 0.0), (0.0, -1e-9), (1e-9, 1e-9)] {
+                let (new_lat, new_lon) = mercator.viewport_pixel_to_lat_lon(lat, lon, zoom, dx, dy);
+                assert!(approx_eq_deg(new_lat, lat) && approx_eq_deg(new_lon, lon), "{} {}: {} {}", dx, dy, new_lat, new_lon);
+            }
+        }
+
+        // Right and down on screen is east and south
+        let (east_lat, east_lon) = mercator.viewport_pixel_to_lat_lon(lat, lon, 12.0, 10.0, 0.0);
+        assert!(approx_eq_deg(east_lat, lat) && east_lon > lon, "{} {}", east_lat, east_lon);
+        let (south_lat, south_lon) = mercator.viewport_pixel_to_lat_lon(lat, lon, 12.0, 0.0, 10.0);
+        assert!(south_lat < lat && approx_eq_deg(south_lon, lon), "{} {}", south_lat, south_lon);
+
+        // One tile to the right and up lands on the matching point of the north-east neighbor
+        let tile_size = mercator.tile_size() as f64;
+        let (tx, ty) = mercator.lat_lon_to_tile(lat, lon, 12);
+        let (new_lat, new_lon) = mercator.viewport_pixel_to_lat_lon(lat, lon, 12.0, tile_size, -tile_size);
+        assert_eq!(mercator.lat_lon_to_tile(new_lat, new_lon, 12), (tx + 1, ty + 1));
+
+        // A pixel covers half as much ground one zoom deeper
+        let (_, lon_12) = mercator.viewport_pixel_to_lat_lon(lat, lon, 12.0, 100.0, 0.0);
+        let (_, lon_13) = mercator.viewport_pixel_to_lat_lon(lat, lon, 13.0, 100.0, 0.0);
+        assert!(approx_eq_deg(lon_12 - lon, 2.0 * (lon_13 - lon)), "{} {}", lon_12, lon_13);
+    }
+
+    #[test]
+    fn test_tile_bounds_in() {
+        let mercator = GlobalMercator::default();
+
+        for &(tx, ty, zoom) in &[(0, 0, 0), (134, 90, 8), (1000, 17, 12)] {
+            assert_eq!(mercator.tile_bounds_in(tx, ty, zoom, Crs::Meters), mercator.tile_bounds(tx, ty, zoom));
+            let (min_lat, min_lon, max_lat, max_lon) = mercator.tile_lat_lon_bounds(tx, ty, zoom);
+            assert_eq!(mercator.tile_bounds_in(tx, ty, zoom, Crs::Degrees), (min_lon, min_lat, max_lon, max_lat));
+        }
+
+        let top_left = mercator.with_origin(Origin::TopLeft);
+        assert_eq!(top_left.tile_bounds_in(134, 165, 8, Crs::Degrees), mercator.tile_bounds_in(134, 90, 8, Crs::Degrees));
+    }
+
+    #[test]
+    fn test_tile_bounds_buffered() {
+        let mercator = GlobalMercator::default();
+        let (tx, ty, zoom) = (134, 90, 8);
+
+        assert_eq!(mercator.tile_bounds_buffered(tx, ty, zoom, 0.0), mercator.tile_bounds(tx, ty, zoom));
+
+        let (minx, miny, maxx, maxy) = mercator.tile_bounds(tx, ty, zoom);
+        let (bminx, bminy, bmaxx, bmaxy) = mercator.tile_bounds_buffered(tx, ty, zoom, 16.0);
+        let buffer = 16.0 * mercator.resolution(zoom);
+        for &grown in &[minx - bminx, miny - bminy, bmaxx - maxx, bmaxy - maxy] {
+            assert!(approx_eq_meters(grown, buffer), "{} != {}", grown, buffer);
+        }
+        // 256 + 2 * 16 pixels across
+        assert!(approx_eq_meters(bmaxx - bminx, 288.0 * mercator.resolution(zoom)), "{}", bmaxx - bminx);
+    }
+
+    #[test]
+    fn test_standard_tables() {
+        let mercator = GlobalMercator::new(256);
+
+        for zoom in 0..23 {
+            let (resolution, scale) = (STANDARD_RESOLUTIONS[zoom], STANDARD_SCALE_DENOMINATORS[zoom]);
+            assert!((resolution - mercator.resolution(zoom as u32)).abs() <= resolution * f64::EPSILON, "zoom {}: {}", zoom, resolution);
+            assert!((scale - mercator.scale_denominator(zoom as u32)).abs() <= scale * f64::EPSILON, "zoom {}: {}", zoom, scale);
+        }
+    }
+
+    #[test]
+    fn test_scale_denominator() {
+        let mercator = GlobalMercator::default();
+
+        let scale = mercator.scale_denominator(0);
+        assert!((scale - 559082264.0287178).abs() < 1e-6, "{}", scale);
+        assert!((mercator.scale_denominator(1) - scale / 2.0).abs() < 1e-6);
+
+        for zoom in 0..24 {
+            let fractional = mercator.zoom_for_scale_denominator(mercator.scale_denominator(zoom));
+            assert!((fractional - zoom as f64).abs() < 1e-9, "{} != {}", fractional, zoom);
+        }
+        // 1:25000 is between zooms 14 (1:34123) and 15 (1:17062), closer to 14
+        let zoom = mercator.zoom_for_scale_denominator(25000.0);
+        assert!(14.0 < zoom && zoom < 14.5, "{}", zoom);
+    }
+
+    #[test]
+    fn test_ground_resolution() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.ground_resolution(0.0, 10.0), mercator.resolution(10));
+        let half = mercator.ground_resolution(60.0, 10.0);
+        assert!((half - mercator.resolution(10) / 2.0).abs() < 1e-9, "{}", half);
+        assert_eq!(mercator.ground_resolution(-60.0, 10.0), half);
+
+        let between = mercator.ground_resolution(0.0, 10.5);
+        assert!(mercator.resolution(11) < between && between < mercator.resolution(10));
+
+        // No further shrinking past the edge of the map
+        assert_eq!(mercator.ground_resolution(90.0, 3.0), mercator.ground_resolution(MAX_LATITUDE, 3.0));
+        assert!(mercator.ground_resolution(90.0, 3.0) > 0.0);
+    }
+
+    #[test]
+    fn test_pixels_length() {
+        let mercator = GlobalMercator::default();
+
+        let width = mercator.meters_to_pixels_length(156543.0, 0.0, 0.0);
+        assert!((width - 1.0).abs() < 1e-4, "{}", width);
+
+        // A 500m feature at 60° spans twice as many pixels as on the equator
+        let equator = mercator.meters_to_pixels_length(500.0, 0.0, 15.0);
+        let north = mercator.meters_to_pixels_length(500.0, 60.0, 15.0);
+        assert!((north / equator - 2.0).abs() < 1e-9, "{} / {}", north, equator);
+        assert_eq!(mercator.meters_to_pixels_length(500.0, 0.0, 16.0), equator * 2.0);
+
+        let length = mercator.pixels_to_meters_length(north, 60.0, 15.0);
+        assert!(approx_eq_meters(length, 500.0), "{}", length);
+    }
+
+    #[test]
+    fn test_point_scale_factor() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.point_scale_factor(0.0), 1.0);
+        assert!((mercator.point_scale_factor(60.0) - 2.0).abs() < 1e-9, "{}", mercator.point_scale_factor(60.0));
+        assert_eq!(mercator.point_scale_factor(-60.0), mercator.point_scale_factor(60.0));
+        assert!(mercator.point_scale_factor(MAX_LATITUDE) > 11.0);
+        assert!(mercator.point_scale_factor(90.0) > 1e15);
+
+        // The ground resolution is the map resolution shrunk by the same factor
+        let ground = mercator.ground_resolution(45.0, 7.0) * mercator.point_scale_factor(45.0);
+        assert!((ground - mercator.resolution(7)).abs() < 1e-9, "{}", ground);
+        assert_eq!(GlobalMercator::new(512).point_scale_factor(45.0), mercator.point_scale_factor(45.0));
+    }
+
+    #[test]
+    fn test_resolution_for_extent_pixels() {
+        let mercator = GlobalMercator::default();
+
+        // A quarter of the world over 1024 pixels is the 4096 pixel wide map of zoom 4
+        let resolution = mercator.resolution_for_extent_pixels(0.0, 90.0, 1024.0);
+        assert!((resolution - mercator.resolution(4)).abs() < 1e-9, "{}", resolution);
+        assert!((resolution - 9783.94).abs() < 0.01, "{}", resolution);
+        assert_eq!(mercator.resolution_for_extent_pixels(-45.0, 45.0, 1024.0), resolution);
+        assert_eq!(mercator.resolution_for_extent_pixels(90.0, 0.0, 1024.0), resolution);
+    }
+
+    #[test]
+    fn test_meters_per_degree() {
+        let mercator = GlobalMercator::default();
+        let degree = mercator.haversine_distance(0.0, 0.0, 1.0, 0.0);
+
+        // On the equator both agree, and a degree is the same length both ways
+        let (lon, lat) = mercator.meters_per_degree(0.0);
+        assert!((lon - 111_319.49).abs() < 0.01 && (lat - degree).abs() < 1e-6, "{}, {}", lon, lat);
+        assert_eq!(mercator.mercator_meters_per_degree(0.0), (lon, lat));
+
+        let (lon, lat) = mercator.meters_per_degree(45.0);
+        assert!((lon / lat - 0.5f64.sqrt()).abs() < 1e-12, "{} / {}", lon, lat);
+        assert_eq!(lat, mercator.meters_per_degree(0.0).1);
+
+        let (map_lon, map_lat) = mercator.mercator_meters_per_degree(45.0);
+        assert_eq!(map_lon, mercator.origin_shift / 180.0);
+        assert!((map_lat / map_lon - 2f64.sqrt()).abs() < 1e-12, "{} / {}", map_lat, map_lon);
+
+        // Matches the projection's local derivative
+        let (_, y1) = mercator.lat_lon_to_meters(44.9995, 0.0);
+        let (_, y2) = mercator.lat_lon_to_meters(45.0005, 0.0);
+        assert!(((y2 - y1) / 0.001 - map_lat).abs() < 1e-3, "{} != {}", (y2 - y1) / 0.001, map_lat);
+    }
+
+    #[test]
+    fn test_fractional_zoom_for_resolution() {
+        let mercator = GlobalMercator::default();
+
+        for zoom in 0..24 {
+            let fractional = mercator.fractional_zoom_for_resolution(mercator.resolution(zoom));
+            assert!((fractional - zoom as f64).abs() < 1e-9, "{} != {}", fractional, zoom);
+        }
+
+        let between = mercator.fractional_zoom_for_resolution((mercator.resolution(7) + mercator.resolution(8)) / 2.0);
+        assert!(7.0 < between && between < 8.0, "{}", between);
+        assert!((mercator.fractional_zoom_for_resolution(mercator.resolution(0) * 4.0) + 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_zoom_ratio() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.zoom_ratio(8, 10), 4.0);
+        assert_eq!(mercator.zoom_ratio(10, 8), 0.25);
+        assert_eq!(mercator.zoom_ratio(5, 5), 1.0);
+        assert_eq!(mercator.zoom_ratio(8, 10), mercator.resolution(8) / mercator.resolution(10));
+        assert_eq!(mercator.zoom_ratio(0, 30), mercator.num_tiles(30) as f64);
+    }
+
+    #[test]
+    fn test_tile_scale_factor() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.tile_scale_factor(8, 9.0), 2.0);
+        assert_eq!(mercator.tile_scale_factor(8, 8.0), 1.0);
+        assert_eq!(mercator.tile_scale_factor(8, 7.0), 0.5);
+        let scale = mercator.tile_scale_factor(8, 8.5);
+        assert!((scale - 2f64.sqrt()).abs() < 1e-12, "{}", scale);
+        assert_eq!(mercator.tile_scale_factor(8, 10.0), mercator.zoom_ratio(8, 10));
+    }
+
+    #[test]
+    fn test_num_tiles_map_size() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.num_tiles(0), 1);
+        assert_eq!(mercator.map_size(0), 256);
+
+        assert_eq!(mercator.num_tiles(20), 1_048_576);
+        assert_eq!(mercator.map_size(20), 268_435_456);
+        assert_eq!(GlobalMercator::new(512).map_size(24), 8_589_934_592);
+    }
+
+    #[test]
+    fn test_tile_count_for_bbox() {
+        let mercator = GlobalMercator::default();
+
+        let (min_lat, min_lon, max_lat, max_lon) = (40.0, -10.0, 60.0, 30.0);
+        let count = mercator.tile_count_for_bbox(min_lat, min_lon, max_lat, max_lon, 6);
+        assert_eq!(count, mercator.tiles_for_bbox(min_lat, min_lon, max_lat, max_lon, 6).count() as u64);
+        assert_eq!(count, 8 * 7);
+
+        assert_eq!(mercator.tile_count_for_bbox(-90.0, -180.0, 90.0, 180.0, 3), 64);
+        assert_eq!(mercator.tile_count_for_bbox(-90.0, -180.0, 90.0, 180.0, 30), 1 << 60);
+        // The range reaches i32::MAX on both axes, the width has to be computed in i64
+        assert_eq!(mercator.tile_count_for_bbox(-90.0, -180.0, 90.0, 180.0, 31), 1 << 62);
+    }
+
+    #[test]
+    fn test_origin() {
+        let tms = GlobalMercator::default();
+        let xyz = GlobalMercator::default().with_origin(Origin::TopLeft);
+        assert_eq!(tms.origin(), Origin::BottomLeft);
+
+        // Oslo is near the top of the map, so its Google row is small
+        let (mx, my) = tms.lat_lon_to_meters(59.9, 10.7);
+        let (tx, ty) = xyz.meters_to_tile(mx, my, 10);
+        assert!(ty < 512, "{}", ty);
+        let (tms_x, tms_y) = tms.meters_to_tile(mx, my, 10);
+        assert_eq!((tx as i64, ty as i64), tms.google_tile(tms_x, tms_y, 10));
+        assert_eq!(xyz.lat_lon_to_google_tile(59.9, 10.7, 10), (tx as i64, ty as i64));
+
+        // The bounds of a tile are the same whichever way it is addressed
+        let (gx, gy) = tms.google_tile(134, 90, 8);
+        assert_eq!(xyz.tile_bounds(gx as i32, gy as i32, 8), tms.tile_bounds(134, 90, 8));
+        let (minx, miny, maxx, maxy) = xyz.tile_bounds(tx, ty, 10);
+        assert!(minx <= mx && mx < maxx && miny <= my && my < maxy);
+
+        let (min_lat, min_lon, max_lat, max_lon) = (40.0, -10.0, 60.0, 30.0);
+        let mut google: Vec<_> = tms.tiles_for_bbox(min_lat, min_lon, max_lat, max_lon, 5).map(|(tx, ty)| tms.google_tile(tx, ty, 5)).collect();
+        let mut tiles: Vec<_> = xyz.tiles_for_bbox(min_lat, min_lon, max_lat, max_lon, 5).map(|(tx, ty)| (tx as i64, ty as i64)).collect();
+        google.sort();
+        tiles.sort();
+        assert_eq!(tiles, google);
+
+        let (mx, my) = tms.lat_lon_to_meters(0.0, 0.0);
+        assert_eq!(xyz.meters_to_tile_snapped(mx, my, 12, 1e-6), (2048, 2047));
+    }
+
+    #[test]
+    fn test_zoom_for_tile_budget() {
+        let mercator = GlobalMercator::default();
+
+        // Europe
+        let (min_lat, min_lon, max_lat, max_lon) = (36.0, -10.0, 71.0, 40.0);
+        let zoom = mercator.zoom_for_tile_budget(min_lat, min_lon, max_lat, max_lon, 4);
+        assert!(zoom <= 3, "{}", zoom);
+        assert!(mercator.tile_count_for_bbox(min_lat, min_lon, max_lat, max_lon, zoom) <= 4);
+        assert!(mercator.tile_count_for_bbox(min_lat, min_lon, max_lat, max_lon, zoom + 1) > 4);
+
+        // A city block
+        let (min_lat, min_lon, max_lat, max_lon) = (48.8580, 2.2940, 48.8590, 2.2960);
+        let zoom = mercator.zoom_for_tile_budget(min_lat, min_lon, max_lat, max_lon, 64);
+        assert!(zoom >= 16, "{}", zoom);
+        assert!(mercator.tile_count_for_bbox(min_lat, min_lon, max_lat, max_lon, zoom) <= 64);
+        assert!(mercator.tile_count_for_bbox(min_lat, min_lon, max_lat, max_lon, zoom + 1) > 64);
+
+        assert_eq!(mercator.zoom_for_tile_budget(-90.0, -180.0, 90.0, 180.0, 0), 0);
+        assert_eq!(mercator.zoom_for_tile_budget(-90.0, -180.0, 90.0, 180.0, 16), 2);
+    }
+
+    #[test]
+    fn test_enclosing_tile() {
+        let mercator = GlobalMercator::default();
+
+        // A few meters around the Eiffel Tower
+        let (tx, ty, zoom) = mercator.enclosing_tile(48.85826, 2.29450, 48.85830, 2.29455);
+        assert!(zoom >= 16, "{}", zoom);
+        assert_eq!(mercator.lat_lon_to_tile(48.85826, 2.29450, zoom), (tx, ty));
+        assert_eq!(mercator.lat_lon_to_tile(48.85830, 2.29455, zoom), (tx, ty));
+        assert_ne!(mercator.lat_lon_to_tile(48.85826, 2.29450, zoom + 1), mercator.lat_lon_to_tile(48.85830, 2.29455, zoom + 1));
+
+        // Australia
+        let (_, _, zoom) = mercator.enclosing_tile(-43.6, 113.3, -10.7, 153.6);
+        assert!(zoom <= 3, "{}", zoom);
+
+        assert_eq!(mercator.enclosing_tile(-10.0, -10.0, 10.0, 10.0), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_pyramid_tiles() {
+        let mercator = GlobalMercator::default();
+        let (min_lat, min_lon, max_lat, max_lon) = (40.0, -10.0, 60.0, 30.0);
+
+        let expected: u64 = (2..=7).map(|zoom| mercator.tile_count_for_bbox(min_lat, min_lon, max_lat, max_lon, zoom)).sum();
+        assert_eq!(mercator.pyramid_tiles(min_lat, min_lon, max_lat, max_lon, 2, 7).count() as u64, expected);
+
+        let first: Vec<_> = mercator.pyramid_tiles(min_lat, min_lon, max_lat, max_lon, 2, 7).take(2).collect();
+        assert!(first.iter().all(|&(_, _, zoom)| zoom == 2));
+
+        // Clamped at every zoom on its own
+        let world: Vec<_> = mercator.pyramid_tiles(-90.0, -180.0, 90.0, 180.0, 0, 2).collect();
+        assert_eq!(world.len(), 1 + 4 + 16);
+        assert!(world.iter().all(|&(tx, ty, zoom)| (0..1 << zoom).contains(&tx) && (0..1 << zoom).contains(&ty)));
+
+        // Effectively unbounded pyramids are fine as long as they are consumed lazily
+        assert_eq!(mercator.pyramid_tiles(-90.0, -180.0, 90.0, 180.0, 20, 30).take(10).count(), 10);
+    }
+
+    #[test]
+    fn test_zoom_for_viewport() {
+        let mercator = GlobalMercator::default();
+
+        // 10km across 1000 pixels is 10 meters/pixel, between zooms 13 (19.1) and 14 (9.6)
+        assert_eq!(mercator.zoom_for_viewport(10_000.0, 1000.0), Ok(13));
+        assert_eq!(mercator.zoom_for_viewport_at_lat(10_000.0, 1000.0, 0.0), Ok(13));
+
+        // At 60°N the same ground distance spans twice as many Mercator meters, so one zoom less
+        // fits it
+        assert_eq!(mercator.zoom_for_viewport_at_lat(10_000.0, 1000.0, 60.0), Ok(12));
+        assert_eq!(mercator.zoom_for_viewport_at_lat(10_000.0, 1000.0, -60.0), Ok(12));
+    }
+
+    #[test]
+    fn test_fit_bounds() {
+        let mercator = GlobalMercator::default();
+
+        // Roughly France, about 1450km square in Mercator meters: 590 by 585 pixels at zoom 6
+        let (min_lat, min_lon, max_lat, max_lon) = (42.3, -4.8, 51.1, 8.2);
+        let (lat, lon, zoom) = mercator.fit_bounds(min_lat, min_lon, max_lat, max_lon, (800.0, 600.0));
+        assert_eq!(zoom, 6);
+        assert!(approx_eq_deg(lon, (min_lon + max_lon) / 2.0), "{}", lon);
+        assert!(min_lat < lat && lat < max_lat, "{}", lat);
+
+        // The bbox fits at that zoom but not one deeper
+        let (x1, y1) = mercator.lat_lon_to_meters(min_lat, min_lon);
+        let (x2, y2) = mercator.lat_lon_to_meters(max_lat, max_lon);
+        let fits = |zoom: u32| (x2 - x1) / mercator.resolution(zoom) <= 800.0 && (y2 - y1) / mercator.resolution(zoom) <= 600.0;
+        assert!(fits(zoom) && !fits(zoom + 1));
+
+        // The center is the one on the map, north of the average latitude
+        let (mx, my) = mercator.lat_lon_to_meters(lat, lon);
+        assert!(approx_eq_meters(mx, (x1 + x2) / 2.0) && approx_eq_meters(my, (y1 + y2) / 2.0));
+        assert!(lat > (min_lat + max_lat) / 2.0);
+
+        // Corners in either order, and a single point zooms all the way in
+        assert_eq!(mercator.fit_bounds(max_lat, max_lon, min_lat, min_lon, (800.0, 600.0)).2, zoom);
+        assert_eq!(mercator.fit_bounds(48.6, 2.3, 48.6, 2.3, (800.0, 600.0)).2, 29);
+    }
+
+    #[test]
+    fn test_zoom_for_pixel_size() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.zoom_for_pixel_size(mercator.resolution(0) * 2.0), Ok(0));
+        assert_eq!(mercator.zoom_for_pixel_size(mercator.resolution(8) * 1.5), Ok(7));
+
+        let pixel_size = mercator.resolution(29) / 2.0;
+        assert_eq!(mercator.zoom_for_pixel_size(pixel_size), Err(GlobalMercatorError::InvalidZoom { pixel_size, searched: 0..30 }));
+    }
+
+    #[test]
+    fn test_zoom_depth_to_resolution() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.zoom_depth_to_resolution(12, mercator.resolution(8)), 4);
+        assert_eq!(mercator.zoom_depth_to_resolution(12, mercator.resolution(8) * 1.5), 5);
+        assert_eq!(mercator.zoom_depth_to_resolution(12, mercator.resolution(12)), 0);
+        assert_eq!(mercator.zoom_depth_to_resolution(12, 1e6), 12);
+
+        // Finer than the source, no overview needed
+        assert_eq!(mercator.zoom_depth_to_resolution(12, mercator.resolution(14)), 0);
+        assert_eq!(mercator.zoom_depth_to_resolution(12, mercator.resolution(29) / 2.0), 0);
+        assert_eq!(mercator.zoom_depth_to_resolution(31, mercator.resolution(29) / 2.0), 2);
+    }
+
+    #[test]
+    fn test_google_tile_zoom_zero() {
+        let mercator = GlobalMercator::default();
+
+        // The root tile is row 0 from either origin
+        assert_eq!(mercator.google_tile(0, 0, 0), (0, 0));
+        assert_eq!(mercator.google_to_tms(0, 0, 0), (0, 0));
+        assert_eq!(mercator.try_google_tile(0, 0, 0), Ok((0, 0)));
+
+        for &(tx, ty) in &[(1, 0), (0, 1), (-1, 0), (0, -1)] {
+            assert_eq!(mercator.try_google_tile(tx, ty, 0), Err(GlobalMercatorError::TileOutOfRange { tx, ty, zoom: 0 }));
+        }
+        assert_eq!(mercator.try_google_tile(3, 2, 3), Ok(mercator.google_tile(3, 2, 3)));
+        assert_eq!(mercator.try_google_tile(3, 8, 3), Err(GlobalMercatorError::TileOutOfRange { tx: 3, ty: 8, zoom: 3 }));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "doesn't exist at zoom 0")]
+    fn test_google_tile_zoom_zero_invalid() {
+        GlobalMercator::default().google_tile(0, 1, 0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_quad_tree_zoom_zero() {
+        let mercator = GlobalMercator::default();
+
+        // No digits for the root tile, and no way back from the empty key
+        assert_eq!(mercator.quad_tree(0, 0, 0), "");
+        assert_eq!(mercator.try_quad_tree(0, 0, 0), Ok(String::new()));
+        assert_eq!(mercator.quad_tree_to_tile(""), Err(GlobalMercatorError::EmptyQuadKey));
+        assert!(mercator.quad_key(0, 0, 0).is_err());
+
+        assert_eq!(mercator.try_quad_tree(1, 1, 0), Err(GlobalMercatorError::TileOutOfRange { tx: 1, ty: 1, zoom: 0 }));
+        assert_eq!(mercator.try_quad_tree(3, 2, 3), Ok(String::from("213")));
+        // "0" would be tile (0, 1) of zoom 1, not (2, 1)
+        assert_eq!(mercator.try_quad_tree(2, 1, 1), Err(GlobalMercatorError::TileOutOfRange { tx: 2, ty: 1, zoom: 1 }));
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", debug_assertions))]
+    #[should_panic(expected = "doesn't exist at zoom 0")]
+    fn test_quad_tree_zoom_zero_invalid() {
+        GlobalMercator::default().quad_tree(1, 0, 0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_quad_tree() {
+        let mercator = GlobalMercator::default();
+
+        // Bing Maps reference tile: XYZ (3, 5) at level 3 is TMS (3, 2)
+        assert_eq!(mercator.quad_tree(3, 2, 3), "213");
+        assert_eq!(mercator.quad_tree_to_tile("213"), Ok((3, 2, 3)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_quad_key() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.quad_key(3, 2, 3).as_deref(), Ok("213"));
+        for &(tx, ty, zoom) in &[(0, 0, 1), (1, 0, 1), (5, 9, 4), (2047, 1536, 11), (134, 90, 8), (0, 0, 23)] {
+            assert_eq!(mercator.quad_key(tx, ty, zoom), Ok(mercator.quad_tree(tx, ty, zoom)));
+        }
+        assert_eq!(mercator.quad_key(0, 0, 23).map(|quad_key| quad_key.len()), Ok(23));
+
+        assert_eq!(mercator.quad_key(0, 0, 0), Err(GlobalMercatorError::ZoomOutOfRange { zoom: 0, supported: 1..24 }));
+        assert_eq!(mercator.quad_key(0, 0, 24), Err(GlobalMercatorError::ZoomOutOfRange { zoom: 24, supported: 1..24 }));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_quad_tree_round_trip() {
+        let mercator = GlobalMercator::default();
+
+        let (tx, ty, zoom) = mercator.quad_tree_to_tile("120220011203").unwrap();
+        assert_eq!(zoom, 12);
+        assert_eq!(mercator.quad_tree(tx, ty, zoom), "120220011203");
+
+        for &(tx, ty, zoom) in &[(0, 0, 1), (1, 0, 1), (5, 9, 4), (2047, 1536, 11)] {
+            let quad_key = mercator.quad_tree(tx, ty, zoom);
+            assert_eq!(mercator.quad_tree_to_tile(&quad_key), Ok((tx, ty, zoom)));
+        }
+    }
+
+    #[test]
+    fn test_quad_tree_to_tile_invalid() {
+        let mercator = GlobalMercator::default();
+        assert_eq!(mercator.quad_tree_to_tile("1204"), Err(GlobalMercatorError::InvalidQuadKey { position: 3, found: '4' }));
+        assert_eq!(mercator.quad_tree_to_tile("12a"), Err(GlobalMercatorError::InvalidQuadKey { position: 2, found: 'a' }));
+        assert_eq!(mercator.quad_tree_to_tile("12x20"), Err(GlobalMercatorError::InvalidQuadKey { position: 2, found: 'x' }));
+        assert_eq!(mercator.quad_tree_to_tile("x"), Err(GlobalMercatorError::InvalidQuadKey { position: 0, found: 'x' }));
+        // Positions count characters, not bytes
+        assert_eq!(mercator.quad_tree_to_tile("1é"), Err(GlobalMercatorError::InvalidQuadKey { position: 1, found: 'é' }));
+        assert_eq!(mercator.quad_tree_to_tile(""), Err(GlobalMercatorError::EmptyQuadKey));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_quad_tree_max_zoom() {
+        let mercator = GlobalMercator::default();
+
+        // 31 digits is the deepest key whose tiles fit in i32 indices
+        let last = i32::MAX;
+        let quad_key = "3".repeat(31);
+        assert_eq!(mercator.quad_tree_to_tile(&quad_key), Ok((last, 0, 31)));
+        assert_eq!(mercator.try_quad_tree(last, 0, 31), Ok(quad_key));
+
+        for &len in &[32, 33, 64] {
+            let zoom = len as u32;
+            for digit in &["0", "1", "3"] {
+                let quad_key = digit.repeat(len);
+                assert_eq!(mercator.quad_tree_to_tile(&quad_key), Err(GlobalMercatorError::ZoomOutOfRange { zoom, supported: 1..32 }));
+            }
+            assert_eq!(mercator.try_quad_tree(0, 0, zoom), Err(GlobalMercatorError::ZoomOutOfRange { zoom, supported: 0..32 }));
+        }
+        assert_eq!(mercator.try_quad_tree(0, 0, 40), Err(GlobalMercatorError::ZoomOutOfRange { zoom: 40, supported: 0..32 }));
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", debug_assertions))]
+    #[should_panic(expected = "limited to zoom 31")]
+    fn test_quad_tree_past_max_zoom() {
+        GlobalMercator::default().quad_tree(0, 0, 40);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,51 @@
+use alloc::vec::Vec;
+
+use crate::{Bounds, Float, GlobalMercator};
+
+/// One tile of a manifest built by `GlobalMercator::generate_manifest`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TileEntry<T = f64> {
+    pub zoom: u32,
+    pub x: i32,
+    pub y: i32,
+    /// Bounds of the tile in degrees, `x` as longitude and `y` as latitude
+    pub bounds_lat_lon: Bounds<T>,
+}
+
+impl<T: Float> GlobalMercator<T> {
+    /// Lists every tile covering the given lat/lon rectangle from `min_zoom` to `max_zoom`, in
+    /// the order of `pyramid_tiles`, with its bounds.
+    pub fn generate_manifest(&self, min_lat: T, min_lon: T, max_lat: T, max_lon: T, min_zoom: u32, max_zoom: u32) -> Vec<TileEntry<T>> {
+        self.pyramid_tiles(min_lat, min_lon, max_lat, max_lon, min_zoom, max_zoom)
+            .map(|(x, y, zoom)| TileEntry {
+                zoom,
+                x,
+                y,
+                bounds_lat_lon: self.tile_lat_lon_bounds_struct(x, y, zoom),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_manifest() {
+        let mercator = GlobalMercator::default();
+        let (min_lat, min_lon, max_lat, max_lon) = (40.0, -10.0, 60.0, 30.0);
+
+        let manifest = mercator.generate_manifest(min_lat, min_lon, max_lat, max_lon, 3, 6);
+        assert_eq!(manifest.len(), mercator.pyramid_tiles(min_lat, min_lon, max_lat, max_lon, 3, 6).count());
+
+        for entry in &manifest {
+            let bounds = entry.bounds_lat_lon;
+            assert!(bounds.min_x < bounds.max_x && bounds.min_y < bounds.max_y, "{:?}", entry);
+            assert_eq!(bounds, mercator.tile_lat_lon_bounds_struct(entry.x, entry.y, entry.zoom));
+        }
+        assert_eq!(manifest.first().map(|entry| entry.zoom), Some(3));
+        assert_eq!(manifest.last().map(|entry| entry.zoom), Some(6));
+    }
+}
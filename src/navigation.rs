@@ -0,0 +1,217 @@
+use crate::{Float, GlobalMercator, Origin};
+
+/// Compass direction from a tile to one of its eight neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// Column and row step for this direction in TMS coordinates, where rows increase northwards.
+    pub fn offset(self) -> (i32, i32) {
+        match self {
+            Direction::North => (0, 1),
+            Direction::NorthEast => (1, 1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, -1),
+            Direction::South => (0, -1),
+            Direction::SouthWest => (-1, -1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, 1),
+        }
+    }
+}
+
+/// How two tiles of the same zoom touch, as returned by `GlobalMercator::adjacency`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Adjacency {
+    /// The tiles share an edge.
+    Edge,
+    /// The tiles only share a corner.
+    Corner,
+}
+
+impl<T: Float> GlobalMercator<T> {
+    /// Returns the tile next to the given one in direction `dir`.
+    ///
+    /// Rows follow the origin of the pyramid, so north is the next row up on a
+    /// `with_origin(Origin::TopLeft)` pyramid too. Returns `None` when stepping past the top or bottom row. With `wrap_longitude` the column
+    /// wraps around the antimeridian, otherwise the out of range column (`-1` or `2^zoom`) is
+    /// returned as is.
+    pub fn neighbor(&self, tx: i32, ty: i32, zoom: u32, dir: Direction, wrap_longitude: bool) -> Option<(i32, i32)> {
+        let n = self.grid_len(zoom);
+        let (dx, dy) = dir.offset();
+
+        let ny = ty as i64 + self.row_step(dy);
+        if ny < 0 || ny >= n {
+            return None;
+        }
+
+        let nx = tx as i64 + dx as i64;
+        let nx = if wrap_longitude { nx.rem_euclid(n) } else { nx };
+        Some((nx as i32, ny as i32))
+    }
+
+    /// Returns the tile `dx` columns east and `dy` rows north of the given one, e.g. to walk
+    /// rings of tiles around a center for prefetching. Like `neighbor`, north follows the origin
+    /// of the pyramid.
+    ///
+    /// Unlike `neighbor`, returns `None` whenever the result leaves the grid, unless
+    /// `wrap_longitude` is set: then the column wraps around the antimeridian, possibly several
+    /// times, and only rows past the top or bottom give `None`.
+    pub fn offset_tile(&self, tx: i32, ty: i32, zoom: u32, dx: i32, dy: i32, wrap_longitude: bool) -> Option<(i32, i32)> {
+        let n = self.grid_len(zoom);
+        let (nx, ny) = (tx as i64 + dx as i64, ty as i64 + self.row_step(dy));
+
+        let nx = if wrap_longitude { nx.rem_euclid(n) } else { nx };
+        if nx < 0 || nx >= n || ny < 0 || ny >= n {
+            return None;
+        }
+        Some((nx as i32, ny as i32))
+    }
+
+    // Row offset for `dy` rows north, which count down from a top-left origin
+    fn row_step(&self, dy: i32) -> i64 {
+        match self.origin {
+            Origin::BottomLeft => dy as i64,
+            Origin::TopLeft => -(dy as i64),
+        }
+    }
+
+    /// Returns `true` if the two tiles of the same zoom share an edge or a corner. A tile isn't
+    /// adjacent to itself.
+    ///
+    /// Columns don't wrap around the antimeridian, so the first and last tiles of a row aren't
+    /// adjacent.
+    pub fn are_adjacent(&self, a: (i32, i32), b: (i32, i32)) -> bool {
+        self.adjacency(a, b).is_some()
+    }
+
+    /// Returns how the two tiles of the same zoom touch, or `None` if they don't or are the same
+    /// tile. Same rules as `are_adjacent`.
+    pub fn adjacency(&self, a: (i32, i32), b: (i32, i32)) -> Option<Adjacency> {
+        let dx = (a.0 as i64 - b.0 as i64).abs();
+        let dy = (a.1 as i64 - b.1 as i64).abs();
+
+        match (dx, dy) {
+            (0, 1) | (1, 0) => Some(Adjacency::Edge),
+            (1, 1) => Some(Adjacency::Corner),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbor() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.neighbor(5, 5, 4, Direction::North, false), Some((5, 6)));
+        assert_eq!(mercator.neighbor(5, 5, 4, Direction::SouthWest, false), Some((4, 4)));
+        assert_eq!(mercator.neighbor(5, 5, 4, Direction::East, false), Some((6, 5)));
+
+        // Off the top and bottom of the pyramid
+        assert_eq!(mercator.neighbor(5, 15, 4, Direction::North, true), None);
+        assert_eq!(mercator.neighbor(5, 15, 4, Direction::NorthWest, true), None);
+        assert_eq!(mercator.neighbor(5, 0, 4, Direction::South, true), None);
+    }
+
+    #[test]
+    fn test_neighbor_wrap_longitude() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.neighbor(15, 3, 4, Direction::East, true), Some((0, 3)));
+        assert_eq!(mercator.neighbor(15, 3, 4, Direction::East, false), Some((16, 3)));
+        assert_eq!(mercator.neighbor(0, 3, 4, Direction::West, true), Some((15, 3)));
+        assert_eq!(mercator.neighbor(0, 3, 4, Direction::West, false), Some((-1, 3)));
+        assert_eq!(mercator.neighbor(0, 0, 0, Direction::East, true), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_neighbor_top_left() {
+        let tms = GlobalMercator::default();
+        let xyz = GlobalMercator::default().with_origin(Origin::TopLeft);
+        let (lat, lon, zoom) = (48.85, 2.35, 8);
+
+        // Both pyramids find the tile north of Paris, in their own rows
+        for mercator in &[tms, xyz] {
+            let (tx, ty) = mercator.lat_lon_to_tile(lat, lon, zoom);
+            let (_, _, max_lat, _) = mercator.tile_lat_lon_bounds(tx, ty, zoom);
+            let north = mercator.lat_lon_to_tile(max_lat + 0.01, lon, zoom);
+            assert_eq!(mercator.neighbor(tx, ty, zoom, Direction::North, false), Some(north), "{:?}", mercator.origin());
+            assert_eq!(mercator.offset_tile(tx, ty, zoom, 0, 1, false), Some(north), "{:?}", mercator.origin());
+        }
+
+        let (tx, ty) = xyz.lat_lon_to_tile(lat, lon, zoom);
+        assert_eq!(xyz.neighbor(tx, ty, zoom, Direction::SouthEast, false), Some((tx + 1, ty + 1)));
+        assert_eq!(xyz.neighbor(tx, 0, zoom, Direction::North, false), None);
+        assert_eq!(xyz.offset_tile(tx, 255, zoom, 0, -1, false), None);
+    }
+
+    #[test]
+    fn test_offset_tile() {
+        let mercator = GlobalMercator::default();
+        let (tx, ty, zoom) = (5, 5, 4);
+
+        let mut ring = Vec::new();
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if (dx, dy) != (0, 0) {
+                    ring.push(mercator.offset_tile(tx, ty, zoom, dx, dy, false).unwrap());
+                }
+            }
+        }
+        assert_eq!(ring, [(4, 4), (5, 4), (6, 4), (4, 5), (6, 5), (4, 6), (5, 6), (6, 6)]);
+
+        // The same steps as the eight compass directions
+        for &dir in &[Direction::North, Direction::SouthEast, Direction::West] {
+            let (dx, dy) = dir.offset();
+            assert_eq!(mercator.offset_tile(tx, ty, zoom, dx, dy, false), mercator.neighbor(tx, ty, zoom, dir, false));
+        }
+
+        assert_eq!(mercator.offset_tile(tx, ty, zoom, 0, 0, false), Some((tx, ty)));
+        assert_eq!(mercator.offset_tile(0, 5, zoom, -1, 0, false), None);
+        assert_eq!(mercator.offset_tile(0, 5, zoom, -1, 0, true), Some((15, 5)));
+        assert_eq!(mercator.offset_tile(15, 5, zoom, 33, 0, true), Some((0, 5)));
+        assert_eq!(mercator.offset_tile(5, 15, zoom, 0, 1, true), None);
+        assert_eq!(mercator.offset_tile(i32::MAX, 0, 31, 1, 0, false), None);
+        assert_eq!(mercator.offset_tile(i32::MAX, 0, 31, 1, 0, true), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_adjacency() {
+        let mercator = GlobalMercator::default();
+        let tile = (5, 5);
+
+        for &b in &[(5, 6), (6, 5), (5, 4), (4, 5)] {
+            assert_eq!(mercator.adjacency(tile, b), Some(Adjacency::Edge), "{:?}", b);
+            assert!(mercator.are_adjacent(tile, b) && mercator.are_adjacent(b, tile), "{:?}", b);
+        }
+        for &b in &[(6, 6), (4, 4), (6, 4), (4, 6)] {
+            assert_eq!(mercator.adjacency(tile, b), Some(Adjacency::Corner), "{:?}", b);
+            assert!(mercator.are_adjacent(tile, b), "{:?}", b);
+        }
+        for &b in &[(5, 5), (5, 7), (7, 7), (3, 5), (-5, -5)] {
+            assert_eq!(mercator.adjacency(tile, b), None, "{:?}", b);
+            assert!(!mercator.are_adjacent(tile, b), "{:?}", b);
+        }
+
+        // Every neighbor is adjacent
+        for &dir in &[Direction::North, Direction::NorthEast, Direction::West] {
+            let b = mercator.neighbor(tile.0, tile.1, 4, dir, false).unwrap();
+            assert!(mercator.are_adjacent(tile, b), "{:?}", dir);
+        }
+        assert!(!mercator.are_adjacent((i32::MIN, 0), (i32::MAX, 0)));
+        assert!(!mercator.are_adjacent((0, 3), (15, 3)));
+    }
+}
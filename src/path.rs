@@ -0,0 +1,135 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::{Float, GlobalMercator};
+
+impl<T: Float> GlobalMercator<T> {
+    /// Returns every tile at `zoom` crossed by the polyline through the given `(lat, lon)`
+    /// points, in the order the path reaches them and without duplicates.
+    ///
+    /// Each segment is walked through the tile grid one tile edge at a time, so the tiles are
+    /// edge-connected even on diagonals. A segment passing exactly through a tile corner steps
+    /// north or south first. Points on a tile edge belong to the tile `lat_lon_to_tile` gives.
+    pub fn tiles_along_path(&self, points: &[(T, T)], zoom: u32) -> Vec<(i32, i32)> {
+        let last = self.grid_len(zoom) - 1;
+        let tile_size = T::from_f64(self.tile_size as f64);
+        let to_grid = |&(lat, lon): &(T, T)| {
+            let (px, py) = self.lat_lon_to_pixels(lat, lon, zoom);
+            ((px / tile_size).to_f64(), (py / tile_size).to_f64())
+        };
+        // Same rule as pixels_to_tile: tile t spans (t, t + 1] in grid units
+        let cell = |g: f64| Float::ceil(g) as i64 - 1;
+
+        let mut tiles = Vec::new();
+        let mut seen = BTreeSet::new();
+        let mut visit = |tx: i64, ty: i64| {
+            let tile = (tx.clamp(0, last) as i32, self.origin_row(ty.clamp(0, last) as i32, zoom));
+            if seen.insert(tile) {
+                tiles.push(tile);
+            }
+        };
+
+        if let Some(first) = points.first() {
+            let (x, y) = to_grid(first);
+            visit(cell(x), cell(y));
+        }
+        for segment in points.windows(2) {
+            let ((x0, y0), (x1, y1)) = (to_grid(&segment[0]), to_grid(&segment[1]));
+            let (mut tx, mut ty) = (cell(x0), cell(y0));
+            let steps = (cell(x1) - tx).abs() + (cell(y1) - ty).abs();
+
+            // Distance along the segment, as a fraction of its length, to the next tile edge on
+            // each axis and between two edges
+            let axis = |start: f64, end: f64, tile: i64| {
+                let delta = end - start;
+                if delta > 0.0 {
+                    (1, (tile as f64 + 1.0 - start) / delta, 1.0 / delta)
+                } else if delta < 0.0 {
+                    (-1, (start - tile as f64) / -delta, 1.0 / -delta)
+                } else {
+                    (0, f64::INFINITY, f64::INFINITY)
+                }
+            };
+            let (step_x, mut next_x, delta_x) = axis(x0, x1, tx);
+            let (step_y, mut next_y, delta_y) = axis(y0, y1, ty);
+
+            for _ in 0..steps {
+                if next_x < next_y {
+                    tx += step_x;
+                    next_x += delta_x;
+                } else {
+                    ty += step_y;
+                    next_y += delta_y;
+                }
+                visit(tx, ty);
+            }
+        }
+        tiles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adjacent(a: (i32, i32), b: (i32, i32)) -> bool {
+        (a.0 - b.0).abs() + (a.1 - b.1).abs() == 1
+    }
+
+    #[test]
+    fn test_diagonal_path() {
+        let mercator = GlobalMercator::default();
+        let start = mercator.tile_center_lat_lon(0, 0, 2);
+        let end = mercator.tile_center_lat_lon(3, 2, 2);
+
+        let tiles = mercator.tiles_along_path(&[start, end], 2);
+        assert_eq!(tiles.len(), 1 + 3 + 2, "{:?}", tiles);
+        assert_eq!(tiles.first(), Some(&(0, 0)));
+        assert_eq!(tiles.last(), Some(&(3, 2)));
+        assert!(tiles.windows(2).all(|pair| adjacent(pair[0], pair[1])), "{:?}", tiles);
+    }
+
+    #[test]
+    fn test_route() {
+        let mercator = GlobalMercator::default();
+        let (london, paris, zoom) = ((51.5074, -0.1278), (48.8566, 2.3522), 10);
+
+        let tiles = mercator.tiles_along_path(&[london, paris], zoom);
+        assert_eq!(tiles.first(), Some(&mercator.lat_lon_to_tile(london.0, london.1, zoom)));
+        assert_eq!(tiles.last(), Some(&mercator.lat_lon_to_tile(paris.0, paris.1, zoom)));
+        assert!(tiles.len() > 2);
+        assert!(tiles.windows(2).all(|pair| adjacent(pair[0], pair[1])), "{:?}", tiles);
+
+        // Going back the same way adds nothing new
+        assert_eq!(mercator.tiles_along_path(&[london, paris, london], zoom), tiles);
+    }
+
+    #[test]
+    fn test_single_tile_path() {
+        let mercator = GlobalMercator::default();
+
+        let tiles = mercator.tiles_along_path(&[(48.60, 2.30), (48.61, 2.31), (48.60, 2.31)], 6);
+        assert_eq!(tiles, [mercator.lat_lon_to_tile(48.6, 2.3, 6)]);
+        assert!(mercator.tiles_along_path(&[], 6).is_empty());
+    }
+
+    #[test]
+    fn test_vertex_on_tile_edge() {
+        let mercator = GlobalMercator::default();
+
+        // The prime meridian is the edge between the two columns of zoom 1
+        let on_edge = (40.0, 0.0);
+        assert_eq!(mercator.tiles_along_path(&[on_edge], 1), [mercator.lat_lon_to_tile(on_edge.0, on_edge.1, 1)]);
+        assert_eq!(mercator.lat_lon_to_tile(on_edge.0, on_edge.1, 1), (0, 1));
+
+        // Ending on the edge stays in the western tile, leaving it eastwards enters the next one
+        assert_eq!(mercator.tiles_along_path(&[(40.0, -20.0), on_edge], 1), [(0, 1)]);
+        assert_eq!(mercator.tiles_along_path(&[on_edge, (40.0, 20.0)], 1), [(0, 1), (1, 1)]);
+
+        let (minx, _, _, maxy) = mercator.tile_bounds(5, 9, 4);
+        let (lat, lon) = mercator.meters_to_lat_lon(minx, maxy);
+        let tiles = mercator.tiles_along_path(&[(lat, lon), mercator.tile_center_lat_lon(5, 9, 4)], 4);
+        assert_eq!(tiles.first(), Some(&mercator.lat_lon_to_tile(lat, lon, 4)));
+        assert_eq!(tiles.last(), Some(&(5, 9)));
+    }
+}
@@ -0,0 +1,183 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Float, GlobalMercator, GlobalMercatorBuilder, Origin, WGS84_RADIUS};
+
+// Only the configuration is serialized, the derived fields are recomputed on deserialization
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "GlobalMercator")]
+struct GlobalMercatorRepr<T: Float> {
+    tile_size: u32,
+    #[serde(default = "default_radius")]
+    radius: T,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_cached_zoom: Option<u32>,
+    #[serde(default, skip_serializing_if = "is_bottom_left")]
+    origin: Origin,
+    #[serde(default = "default_pixel_ratio", skip_serializing_if = "is_one")]
+    pixel_ratio: u32,
+}
+
+fn is_bottom_left(origin: &Origin) -> bool {
+    *origin == Origin::BottomLeft
+}
+
+fn default_pixel_ratio() -> u32 {
+    1
+}
+
+fn is_one(pixel_ratio: &u32) -> bool {
+    *pixel_ratio == 1
+}
+
+fn default_radius<T: Float>() -> T {
+    T::from_f64(WGS84_RADIUS)
+}
+
+impl<T: Float + Serialize> Serialize for GlobalMercator<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GlobalMercatorRepr {
+            tile_size: self.tile_size,
+            radius: self.radius(),
+            max_cached_zoom: self.max_cached_zoom(),
+            origin: self.origin(),
+            pixel_ratio: self.pixel_ratio(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Float + Deserialize<'de>> Deserialize<'de> for GlobalMercator<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = GlobalMercatorRepr::deserialize(deserializer)?;
+        // Validated like any other configuration
+        let mut builder = GlobalMercatorBuilder::default()
+            .tile_size(repr.tile_size)
+            .radius(repr.radius)
+            .origin(repr.origin)
+            .dpi_scale(repr.pixel_ratio);
+        if let Some(max_zoom) = repr.max_cached_zoom {
+            builder = builder.zoom_cache(max_zoom);
+        }
+        builder.build().map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bounds, GlobalGeodetic, GlobalMercator, Origin, Tile, TileJsonExtent, ZoomLevel, WGS84_RADIUS};
+
+    #[test]
+    fn test_mercator_round_trip() {
+        let mercator = GlobalMercator::new(512);
+
+        let json = serde_json::to_string(&mercator).unwrap();
+        assert_eq!(json, r#"{"tile_size":512,"radius":6378137.0}"#);
+
+        let decoded: GlobalMercator = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.tile_size(), 512);
+        assert_eq!(decoded.resolution(3), mercator.resolution(3));
+        assert_eq!(decoded.tile_bounds(1, 2, 3), mercator.tile_bounds(1, 2, 3));
+    }
+
+    #[test]
+    fn test_mercator_defaults() {
+        let decoded: GlobalMercator = serde_json::from_str(r#"{"tile_size":512}"#).unwrap();
+        assert_eq!(decoded.radius(), WGS84_RADIUS);
+        assert_eq!(decoded.resolution(0), GlobalMercator::new(512).resolution(0));
+
+        let decoded: GlobalMercator = serde_json::from_str(r#"{"tile_size":256,"radius":6371000.0}"#).unwrap();
+        assert_eq!(decoded.resolution(0), GlobalMercator::with_radius(256, 6371000.0).resolution(0));
+    }
+
+    #[test]
+    fn test_mercator_zoom_cache() {
+        let mercator: GlobalMercator = GlobalMercator::with_zoom_cache(256, 18);
+
+        let json = serde_json::to_string(&mercator).unwrap();
+        assert_eq!(json, r#"{"tile_size":256,"radius":6378137.0,"max_cached_zoom":18}"#);
+
+        let decoded: GlobalMercator = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.max_cached_zoom(), Some(18));
+        assert_eq!(decoded.resolution(10), mercator.resolution(10));
+
+        // Capped like with_zoom_cache, even for the largest zoom
+        let decoded: GlobalMercator = serde_json::from_str(r#"{"tile_size":256,"max_cached_zoom":4294967295}"#).unwrap();
+        assert_eq!(decoded.max_cached_zoom(), GlobalMercator::<f64>::with_zoom_cache(256, u32::MAX).max_cached_zoom());
+    }
+
+    #[test]
+    fn test_mercator_origin() {
+        let mercator = GlobalMercator::new(256).with_origin(Origin::TopLeft);
+
+        let json = serde_json::to_string(&mercator).unwrap();
+        assert_eq!(json, r#"{"tile_size":256,"radius":6378137.0,"origin":"TopLeft"}"#);
+
+        let decoded: GlobalMercator = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.origin(), Origin::TopLeft);
+    }
+
+    #[test]
+    fn test_mercator_dpi_scale() {
+        let mercator = GlobalMercator::<f64>::with_dpi_scale(256, 2);
+
+        let json = serde_json::to_string(&mercator).unwrap();
+        assert_eq!(json, r#"{"tile_size":256,"radius":6378137.0,"pixel_ratio":2}"#);
+
+        let decoded: GlobalMercator = serde_json::from_str(&json).unwrap();
+        assert_eq!((decoded.tile_size(), decoded.pixel_ratio()), (512, 2));
+        assert_eq!(decoded.resolution(3), mercator.resolution(3));
+    }
+
+    #[test]
+    fn test_mercator_invalid() {
+        for json in &[
+            r#"{"tile_size":0}"#,
+            r#"{"tile_size":256,"pixel_ratio":0}"#,
+            r#"{"tile_size":256,"radius":-1.0}"#,
+            r#"{"tile_size":256,"radius":0.0}"#,
+        ] {
+            let error = serde_json::from_str::<GlobalMercator>(json).unwrap_err();
+            assert!(error.is_data(), "{}: {}", json, error);
+        }
+
+        let error = serde_json::from_str::<GlobalMercator>(r#"{"tile_size":0}"#).unwrap_err();
+        assert_eq!(error.to_string(), "tile size 0 is not a positive number of pixels");
+    }
+
+    #[test]
+    fn test_mercator_ignores_derived_fields() {
+        let decoded: GlobalMercator = serde_json::from_str(r#"{"tile_size":256,"initial_resolution":1.0}"#).unwrap();
+        assert_eq!(decoded.resolution(0), GlobalMercator::new(256).resolution(0));
+    }
+
+    #[test]
+    fn test_types_round_trip() {
+        let tile = Tile::new(134, 90, 8);
+        assert_eq!(serde_json::from_str::<Tile>(&serde_json::to_string(&tile).unwrap()).unwrap(), tile);
+
+        let bounds = GlobalMercator::default().tile_bounds_struct(134, 90, 8);
+        assert_eq!(serde_json::from_str::<Bounds>(&serde_json::to_string(&bounds).unwrap()).unwrap(), bounds);
+
+        let extent = GlobalMercator::default().tilejson_extent(0, 4);
+        let json = serde_json::to_string(&extent).unwrap();
+        assert!(json.contains(r#""center":[0.0,0.0,2],"minzoom":0,"maxzoom":4"#), "{}", json);
+        assert_eq!(serde_json::from_str::<TileJsonExtent>(&json).unwrap(), extent);
+
+        let level = GlobalMercator::default().zoom_levels(3, 3).next().unwrap();
+        assert_eq!(serde_json::from_str::<ZoomLevel>(&serde_json::to_string(&level).unwrap()).unwrap(), level);
+
+        let geodetic: GlobalGeodetic = serde_json::from_str(r#"{"tile_size":512}"#).unwrap();
+        assert_eq!(geodetic.tile_size(), 512);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_manifest() {
+        let manifest = GlobalMercator::default().generate_manifest(40.0, -10.0, 60.0, 30.0, 0, 1);
+
+        let json = serde_json::to_string(&manifest).unwrap();
+        assert!(json.starts_with(r#"[{"zoom":0,"x":0,"y":0,"bounds_lat_lon":{"min_x":-180.0,"#), "{}", json);
+        assert_eq!(serde_json::from_str::<Vec<crate::TileEntry>>(&json).unwrap(), manifest);
+    }
+}
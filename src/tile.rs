@@ -0,0 +1,177 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{Float, GlobalMercator, GlobalMercatorError};
+
+/// A TMS tile address in a `GlobalMercator` pyramid.
+///
+/// `x` and `y` follow the TMS convention used throughout `GlobalMercator` (origin at the
+/// bottom-left corner); use `to_google` to get Google/XYZ coordinates. With a pyramid built
+/// `with_origin(Origin::TopLeft)`, `from_meters`, `bounds` and `lat_lon_bounds` use Google rows
+/// instead, while `quad_tree` and `to_google` still expect TMS rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tile {
+    pub x: i32,
+    pub y: i32,
+    pub zoom: u32,
+}
+
+impl Tile {
+    pub fn new(x: i32, y: i32, zoom: u32) -> Tile {
+        Tile { x, y, zoom }
+    }
+
+    /// Returns the tile covering the given EPSG:900913 coordinates.
+    pub fn from_meters<T: Float>(mercator: &GlobalMercator<T>, mx: T, my: T, zoom: u32) -> Tile {
+        let (x, y) = mercator.meters_to_tile(mx, my, zoom);
+        Tile::new(x, y, zoom)
+    }
+
+    /// Returns the tile addressed by a Microsoft quad key.
+    pub fn from_quad_tree<T: Float>(mercator: &GlobalMercator<T>, quad_key: &str) -> Result<Tile, GlobalMercatorError> {
+        let (x, y, zoom) = mercator.quad_tree_to_tile(quad_key)?;
+        Ok(Tile::new(x, y, zoom))
+    }
+
+    pub fn bounds<T: Float>(&self, mercator: &GlobalMercator<T>) -> (T, T, T, T) {
+        mercator.tile_bounds(self.x, self.y, self.zoom)
+    }
+
+    pub fn lat_lon_bounds<T: Float>(&self, mercator: &GlobalMercator<T>) -> (T, T, T, T) {
+        mercator.tile_lat_lon_bounds(self.x, self.y, self.zoom)
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn quad_tree<T: Float>(&self, mercator: &GlobalMercator<T>) -> String {
+        mercator.quad_tree(self.x, self.y, self.zoom)
+    }
+
+    pub fn to_google<T: Float>(&self, mercator: &GlobalMercator<T>) -> (i64, i64) {
+        mercator.google_tile(self.x, self.y, self.zoom)
+    }
+}
+
+impl From<(i32, i32, u32)> for Tile {
+    fn from((x, y, zoom): (i32, i32, u32)) -> Tile {
+        Tile::new(x, y, zoom)
+    }
+}
+
+impl From<Tile> for (i32, i32, u32) {
+    fn from(tile: Tile) -> (i32, i32, u32) {
+        (tile.x, tile.y, tile.zoom)
+    }
+}
+
+/// Drops the zoom, for the `(tx, ty)` pairs taken by most `GlobalMercator` methods.
+impl From<Tile> for (i32, i32) {
+    fn from(tile: Tile) -> (i32, i32) {
+        (tile.x, tile.y)
+    }
+}
+
+/// Formats the tile as `z/x/y`.
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.zoom, self.x, self.y)
+    }
+}
+
+/// Parses a tile formatted as `z/x/y`.
+impl FromStr for Tile {
+    type Err = GlobalMercatorError;
+
+    fn from_str(s: &str) -> Result<Tile, GlobalMercatorError> {
+        let mut parts = s.split('/');
+        let mut next = || parts.next().ok_or(GlobalMercatorError::InvalidTilePath);
+        let zoom = next()?.parse().map_err(|_| GlobalMercatorError::InvalidTilePath)?;
+        let x = next()?.parse().map_err(|_| GlobalMercatorError::InvalidTilePath)?;
+        let y = next()?.parse().map_err(|_| GlobalMercatorError::InvalidTilePath)?;
+
+        if parts.next().is_some() {
+            return Err(GlobalMercatorError::InvalidTilePath);
+        }
+        Ok(Tile::new(x, y, zoom))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_bounds() {
+        let mercator = GlobalMercator::default();
+        let tile = Tile::new(134, 90, 8);
+
+        assert_eq!(tile.bounds(&mercator), mercator.tile_bounds(134, 90, 8));
+        assert_eq!(tile.lat_lon_bounds(&mercator), mercator.tile_lat_lon_bounds(134, 90, 8));
+        assert_eq!(tile.to_google(&mercator), mercator.google_tile(134, 90, 8));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_quad_tree() {
+        let mercator = GlobalMercator::default();
+        let tile = Tile::new(134, 90, 8);
+
+        assert_eq!(tile.quad_tree(&mercator), mercator.quad_tree(134, 90, 8));
+    }
+
+    #[test]
+    fn test_constructors() {
+        let mercator = GlobalMercator::default();
+        let tile = Tile::new(3, 2, 3);
+
+        assert_eq!(Tile::from_quad_tree(&mercator, "213"), Ok(tile));
+
+        let (minx, miny, maxx, maxy) = tile.bounds(&mercator);
+        let (mx, my) = ((minx + maxx) / 2.0, (miny + maxy) / 2.0);
+        assert_eq!(Tile::from_meters(&mercator, mx, my, 3), tile);
+    }
+
+    #[test]
+    fn test_tuple_conversions() {
+        let mercator = GlobalMercator::default();
+        let tile = Tile::from((134, 90, 8));
+        assert_eq!(tile, Tile::new(134, 90, 8));
+
+        let (x, y, zoom) = tile.into();
+        assert_eq!((x, y, zoom), (134, 90, 8));
+        assert_eq!(Tile::from(<(i32, i32, u32)>::from(tile)), tile);
+
+        let (tx, ty): (i32, i32) = tile.into();
+        assert_eq!(mercator.tile_bounds(tx, ty, zoom), tile.bounds(&mercator));
+
+        let parent: Tile = mercator.parent_tile(tx, ty, zoom).unwrap().into();
+        assert_eq!(parent, Tile::new(67, 45, 7));
+    }
+
+    #[test]
+    fn test_display_from_str() {
+        let tile = Tile::new(134, 90, 8);
+        assert_eq!(tile.to_string(), "8/134/90");
+        assert_eq!(Tile::from_str(&tile.to_string()), Ok(tile));
+        assert_eq!("0/0/0".parse(), Ok(Tile::new(0, 0, 0)));
+
+        for invalid in &["", "8/134", "8/134/90/1", "8/a/90", "-1/0/0", "8/134/90.5", "8//90"] {
+            assert_eq!(invalid.parse::<Tile>(), Err(GlobalMercatorError::InvalidTilePath), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_hash_map_key() {
+        let mut cache = HashMap::new();
+        cache.insert(Tile::new(1, 2, 3), "a");
+        cache.insert(Tile::new(2, 1, 3), "b");
+
+        assert_eq!(cache.get(&Tile::new(1, 2, 3)), Some(&"a"));
+        assert_eq!(cache.get(&Tile::new(2, 1, 3)), Some(&"b"));
+        assert_eq!(cache.get(&Tile::new(1, 2, 4)), None);
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{Float, GlobalMercator};
+
+/// The extent fields of a TileJSON document covering the whole pyramid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TileJsonExtent<T = f64> {
+    /// `[west, south, east, north]` in degrees
+    pub bounds: [T; 4],
+    /// `(lon, lat, zoom)` of the default view
+    pub center: (T, T, u32),
+    #[cfg_attr(feature = "serde", serde(rename = "minzoom"))]
+    pub min_zoom: u32,
+    #[cfg_attr(feature = "serde", serde(rename = "maxzoom"))]
+    pub max_zoom: u32,
+}
+
+impl<T: Float> GlobalMercator<T> {
+    /// Returns the TileJSON `bounds`, `center`, `minzoom` and `maxzoom` of a tile set spanning
+    /// the whole Mercator extent from `min_zoom` to `max_zoom`.
+    ///
+    /// The center is the origin, at the zoom halfway between `min_zoom` and `max_zoom`. An inverted
+    /// zoom range is swapped, TileJSON requires `minzoom <= maxzoom`.
+    pub fn tilejson_extent(&self, min_zoom: u32, max_zoom: u32) -> TileJsonExtent<T> {
+        let (min_zoom, max_zoom) = if min_zoom <= max_zoom { (min_zoom, max_zoom) } else { (max_zoom, min_zoom) };
+        let (south, west) = self.meters_to_lat_lon(-self.origin_shift, -self.origin_shift);
+        let (north, east) = self.meters_to_lat_lon(self.origin_shift, self.origin_shift);
+        let zero = T::from_f64(0.0);
+
+        TileJsonExtent {
+            bounds: [west, south, east, north],
+            center: (zero, zero, min_zoom + (max_zoom - min_zoom) / 2),
+            min_zoom,
+            max_zoom,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MAX_LATITUDE;
+
+    #[test]
+    fn test_tilejson_extent() {
+        let mercator = GlobalMercator::default();
+        let extent = mercator.tilejson_extent(2, 14);
+
+        let expected = [-180.0, -MAX_LATITUDE, 180.0, MAX_LATITUDE];
+        for (value, expected) in extent.bounds.iter().zip(expected.iter()) {
+            assert!((value - expected).abs() < 1e-6, "{} != {}", value, expected);
+        }
+        assert_eq!(extent.center, (0.0, 0.0, 8));
+        assert_eq!((extent.min_zoom, extent.max_zoom), (2, 14));
+    }
+
+    #[test]
+    fn test_tilejson_extent_inverted_zooms() {
+        let mercator = GlobalMercator::default();
+        assert_eq!(mercator.tilejson_extent(5, 2), mercator.tilejson_extent(2, 5));
+        assert_eq!(mercator.tilejson_extent(5, 2).center.2, 3);
+        assert_eq!((mercator.tilejson_extent(31, 0).min_zoom, mercator.tilejson_extent(31, 0).max_zoom), (0, 31));
+        assert_eq!(mercator.tilejson_extent(7, 7).center.2, 7);
+    }
+}
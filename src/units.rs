@@ -0,0 +1,80 @@
+use crate::{Float, GlobalMercator};
+
+/// EPSG:900913 coordinates in meters, `(x, y)`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Meters<T = f64>(pub T, pub T);
+
+/// Pyramid pixel coordinates at some zoom, `(x, y)` with the origin at the bottom-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pixels<T = f64>(pub T, pub T);
+
+/// WGS84 coordinates in degrees, `(lat, lon)` in the same order as `lat_lon_to_meters`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LatLon<T = f64>(pub T, pub T);
+
+/// Tile column and row, `(tx, ty)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TileXy(pub i32, pub i32);
+
+// Typed versions of the raw conversions, so meters, pixels and degrees can't be mixed up
+impl<T: Float> GlobalMercator<T> {
+    pub fn to_meters(&self, coord: LatLon<T>) -> Meters<T> {
+        let (mx, my) = self.lat_lon_to_meters(coord.0, coord.1);
+        Meters(mx, my)
+    }
+
+    pub fn to_lat_lon(&self, coord: Meters<T>) -> LatLon<T> {
+        let (lat, lon) = self.meters_to_lat_lon(coord.0, coord.1);
+        LatLon(lat, lon)
+    }
+
+    pub fn to_pixels(&self, coord: Meters<T>, zoom: u32) -> Pixels<T> {
+        let (px, py) = self.meters_to_pixels(coord.0, coord.1, zoom);
+        Pixels(px, py)
+    }
+
+    pub fn to_tile(&self, coord: Pixels<T>) -> TileXy {
+        let (tx, ty) = self.pixels_to_tile(coord.0, coord.1);
+        TileXy(tx, ty)
+    }
+}
+
+// `GlobalMercator::to_meters` already converts from degrees, so the way back from pixels sits
+// on the pixel type, like the `Tile` methods
+impl<T: Float> Pixels<T> {
+    pub fn to_meters(self, mercator: &GlobalMercator<T>, zoom: u32) -> Meters<T> {
+        let (mx, my) = mercator.pixels_to_meters(self.0, self.1, zoom);
+        Meters(mx, my)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_pipeline() {
+        let mercator = GlobalMercator::default();
+        let (lat, lon, zoom) = (-33.9, 151.2, 12);
+
+        let meters = mercator.to_meters(LatLon(lat, lon));
+        let pixels = mercator.to_pixels(meters, zoom);
+        let tile = mercator.to_tile(pixels);
+
+        let (mx, my) = mercator.lat_lon_to_meters(lat, lon);
+        let (px, py) = mercator.meters_to_pixels(mx, my, zoom);
+        assert_eq!(meters, Meters(mx, my));
+        assert_eq!(pixels, Pixels(px, py));
+        let (tx, ty) = mercator.pixels_to_tile(px, py);
+        assert_eq!(tile, TileXy(tx, ty));
+
+        let (mx, my) = mercator.pixels_to_meters(px, py, zoom);
+        assert_eq!(pixels.to_meters(&mercator, zoom), Meters(mx, my));
+        let back = mercator.to_lat_lon(meters);
+        assert!((back.0 - lat).abs() < 1e-9 && (back.1 - lon).abs() < 1e-9, "{:?}", back);
+    }
+}
@@ -0,0 +1,121 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::{Float, GlobalMercator, Tile};
+
+/// Row convention used when formatting tile URLs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TileScheme {
+    /// TMS rows, counted from the bottom of the map.
+    Tms,
+    /// Google/XYZ (slippy map) rows, counted from the top of the map.
+    Xyz,
+}
+
+impl<T: Float> GlobalMercator<T> {
+    /// Formats a tile URL from a template such as `https://host/{z}/{x}/{y}.png`.
+    ///
+    /// `tx` and `ty` are TMS tile coordinates, `scheme` selects how `{y}` is written. `{quadkey}`
+    /// is replaced with the Microsoft quad key for Bing style URLs. Unknown placeholders are left
+    /// untouched.
+    pub fn tile_url(&self, template: &str, tx: i32, ty: i32, zoom: u32, scheme: TileScheme) -> String {
+        let (x, y) = match scheme {
+            TileScheme::Tms => (tx as i64, ty as i64),
+            TileScheme::Xyz => self.google_tile(tx, ty, zoom),
+        };
+
+        let mut url = template
+            .replace("{z}", &zoom.to_string())
+            .replace("{x}", &x.to_string())
+            .replace("{y}", &y.to_string());
+        if url.contains("{quadkey}") {
+            url = url.replace("{quadkey}", &self.quad_tree(tx, ty, zoom));
+        }
+        url
+    }
+
+    /// Formats the path of a TMS tile in a slippy map directory tree, `z/x/y.ext` with XYZ rows.
+    ///
+    /// `ext` may be given with or without its dot, an empty `ext` leaves the extension out.
+    pub fn to_xyz_path(&self, tx: i32, ty: i32, zoom: u32, ext: &str) -> String {
+        let (x, y) = self.google_tile(tx, ty, zoom);
+        let ext = ext.trim_start_matches('.');
+        if ext.is_empty() {
+            format!("{}/{}/{}", zoom, x, y)
+        } else {
+            format!("{}/{}/{}.{}", zoom, x, y, ext)
+        }
+    }
+
+    /// Parses a `z/x/y` slippy map path, with optional leading slashes and extension, into TMS
+    /// tile coordinates, the inverse of `to_xyz_path`.
+    ///
+    /// Returns `None` for malformed paths and tiles outside the grid of zooms up to 31.
+    pub fn from_xyz_path(&self, path: &str) -> Option<(i32, i32, u32)> {
+        let path = path.trim_start_matches('/');
+        let path = match path.rfind('/') {
+            Some(slash) => path[slash..].find('.').map_or(path, |dot| &path[..slash + dot]),
+            None => path,
+        };
+
+        let tile: Tile = path.parse().ok()?;
+        if tile.zoom > 31 || !self.is_valid_tile(tile.x, tile.y, tile.zoom) {
+            return None;
+        }
+        let (tx, ty) = self.google_to_tms(tile.x, tile.y, tile.zoom);
+        Some((tx, ty, tile.zoom))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_url() {
+        let mercator = GlobalMercator::default();
+        let template = "https://tiles.example.com/{z}/{x}/{y}.png";
+
+        assert_eq!(mercator.tile_url(template, 3, 2, 3, TileScheme::Tms), "https://tiles.example.com/3/3/2.png");
+        assert_eq!(mercator.tile_url(template, 3, 2, 3, TileScheme::Xyz), "https://tiles.example.com/3/3/5.png");
+    }
+
+    #[test]
+    fn test_xyz_path() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.to_xyz_path(3, 2, 3, "png"), "3/3/5.png");
+        assert_eq!(mercator.to_xyz_path(3, 2, 3, ".pbf"), "3/3/5.pbf");
+        assert_eq!(mercator.to_xyz_path(3, 2, 3, ""), "3/3/5");
+
+        for &(tx, ty, zoom) in &[(0, 0, 0), (3, 2, 3), (1000, 17, 12), (5, i32::MAX, 31)] {
+            for ext in &["png", "", "tar.gz"] {
+                let path = mercator.to_xyz_path(tx, ty, zoom, ext);
+                assert_eq!(mercator.from_xyz_path(&path), Some((tx, ty, zoom)), "{}", path);
+            }
+        }
+
+        assert_eq!(mercator.from_xyz_path("/3/3/5.png"), Some((3, 2, 3)));
+        assert_eq!(mercator.from_xyz_path("//3/3/5"), Some((3, 2, 3)));
+        for invalid in &["", "3/3", "3/3/5/1.png", "3/3/a.png", "3/3/8.png", "3/-1/5", "40/0/0", "3/3.png/5"] {
+            assert_eq!(mercator.from_xyz_path(invalid), None, "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_tile_url_quadkey() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(
+            mercator.tile_url("https://t0.example.net/tiles/a{quadkey}.jpeg?g=1", 3, 2, 3, TileScheme::Xyz),
+            "https://t0.example.net/tiles/a213.jpeg?g=1"
+        );
+    }
+
+    #[test]
+    fn test_tile_url_unknown_placeholder() {
+        let mercator = GlobalMercator::default();
+
+        assert_eq!(mercator.tile_url("{s}/{z}/{x}/{y}{r}", 0, 0, 0, TileScheme::Xyz), "{s}/0/0/0{r}");
+    }
+}
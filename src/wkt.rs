@@ -0,0 +1,86 @@
+use alloc::format;
+use alloc::string::String;
+
+use crate::{Crs, Float, GlobalMercator};
+
+/// Coordinate system of the polygons produced by `GlobalMercator::tile_wkt`, the same as the
+/// one of `GlobalMercator::tile_bounds_in`.
+pub type WktCrs = Crs;
+
+impl<T: Float> GlobalMercator<T> {
+    /// Returns the outline of the given tile as a WKT `POLYGON`.
+    ///
+    /// Points are written x then y (easting northing, or lon lat) and the ring is closed.
+    /// Numbers use the shortest representation that round-trips, so neighbouring tiles never
+    /// collapse onto the same coordinates.
+    pub fn tile_wkt(&self, tx: i32, ty: i32, zoom: u32, crs: WktCrs) -> String {
+        let (min_x, min_y, max_x, max_y) = self.tile_bounds_in(tx, ty, zoom, crs);
+        let (min_x, min_y, max_x, max_y) = (min_x.to_f64(), min_y.to_f64(), max_x.to_f64(), max_y.to_f64());
+
+        format!(
+            "POLYGON(({0} {1}, {2} {1}, {2} {3}, {0} {3}, {0} {1}))",
+            min_x, min_y, max_x, max_y
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_ring(wkt: &str) -> Vec<(f64, f64)> {
+        let ring = wkt.trim_start_matches("POLYGON((").trim_end_matches("))");
+        ring.split(", ")
+            .map(|point| {
+                let mut xy = point.split(' ').map(|v| v.parse::<f64>().unwrap());
+                (xy.next().unwrap(), xy.next().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tile_wkt_meters() {
+        let mercator = GlobalMercator::default();
+        let wkt = mercator.tile_wkt(134, 90, 8, WktCrs::Meters);
+
+        let ring = parse_ring(&wkt);
+        let expected = [
+            (939258.2035682462, -5948635.289265556),
+            (1095801.2374962866, -5948635.289265556),
+            (1095801.2374962866, -5792092.255337516),
+            (939258.2035682462, -5792092.255337516),
+            (939258.2035682462, -5948635.289265556),
+        ];
+        assert_eq!(ring.len(), expected.len());
+        for (&(x, y), &(ex, ey)) in ring.iter().zip(expected.iter()) {
+            assert!((x - ex).abs() < 1e-6 && (y - ey).abs() < 1e-6, "({}, {}) != ({}, {})", x, y, ex, ey);
+        }
+    }
+
+    #[test]
+    fn test_tile_wkt_degrees() {
+        let mercator = GlobalMercator::default();
+        let wkt = mercator.tile_wkt(134, 90, 8, WktCrs::Degrees);
+        assert!(wkt.starts_with("POLYGON(("));
+
+        let ring = parse_ring(&wkt);
+        assert_eq!(ring.len(), 5);
+        assert_eq!(ring[0], ring[4]);
+
+        // lon lat order
+        let (min_lon, min_lat) = ring[0];
+        let (max_lon, max_lat) = ring[2];
+        assert!((min_lon - 8.4375).abs() < 1e-9 && (max_lon - 9.84375).abs() < 1e-9, "{} {}", min_lon, max_lon);
+        assert!((min_lat - -47.04018214480666).abs() < 1e-9 && (max_lat - -46.07323062540835).abs() < 1e-9, "{} {}", min_lat, max_lat);
+    }
+
+    #[test]
+    fn test_tile_wkt_precision() {
+        let mercator = GlobalMercator::default();
+        let zoom = 22;
+
+        let ring = parse_ring(&mercator.tile_wkt(1 << 21, 1 << 21, zoom, WktCrs::Degrees));
+        assert!(ring[0].0 < ring[1].0, "{:?}", ring);
+        assert!(ring[1].1 < ring[2].1, "{:?}", ring);
+    }
+}
@@ -0,0 +1,68 @@
+use alloc::vec::Vec;
+
+use crate::{Float, GlobalMercator};
+
+/// Metadata for one zoom level of a WMTS TileMatrixSet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TileMatrix<T = f64> {
+    pub zoom: u32,
+    pub scale_denominator: T,
+    pub matrix_width: u64,
+    pub matrix_height: u64,
+    /// Top-left corner of the matrix in EPSG:900913 meters
+    pub top_left_corner: (T, T),
+    pub tile_size: u32,
+}
+
+impl<T: Float> GlobalMercator<T> {
+    /// Returns the TileMatrix definitions for `min_zoom..=max_zoom`.
+    ///
+    /// With 256 pixel tiles this is the standard GoogleMapsCompatible matrix set.
+    pub fn wmts_tile_matrix_set(&self, min_zoom: u32, max_zoom: u32) -> Vec<TileMatrix<T>> {
+        (min_zoom..=max_zoom)
+            .map(|zoom| TileMatrix {
+                zoom,
+                scale_denominator: self.scale_denominator(zoom),
+                matrix_width: self.num_tiles(zoom),
+                matrix_height: self.num_tiles(zoom),
+                top_left_corner: (-self.origin_shift, self.origin_shift),
+                tile_size: self.tile_size,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wmts_tile_matrix_set() {
+        let mercator = GlobalMercator::default();
+        let matrix_set = mercator.wmts_tile_matrix_set(0, 18);
+
+        assert_eq!(matrix_set.len(), 19);
+
+        let zoom0 = &matrix_set[0];
+        assert!((zoom0.scale_denominator - 559082264.0287178).abs() < 1e-6, "{}", zoom0.scale_denominator);
+        assert_eq!((zoom0.matrix_width, zoom0.matrix_height), (1, 1));
+        assert_eq!(zoom0.top_left_corner, (-20037508.342789244, 20037508.342789244));
+        assert_eq!(zoom0.tile_size, 256);
+
+        let zoom18 = &matrix_set[18];
+        assert_eq!(zoom18.zoom, 18);
+        assert_eq!((zoom18.matrix_width, zoom18.matrix_height), (262144, 262144));
+        assert!((zoom18.scale_denominator - 2132.729583849784).abs() < 1e-6, "{}", zoom18.scale_denominator);
+    }
+
+    #[test]
+    fn test_wmts_tile_matrix_set_past_zoom_63() {
+        let mercator = GlobalMercator::default();
+        let matrix_set = mercator.wmts_tile_matrix_set(63, 64);
+
+        assert_eq!(matrix_set[0].matrix_width, 1 << 63);
+        // Saturated like num_tiles
+        assert_eq!((matrix_set[1].matrix_width, matrix_set[1].matrix_height), (u64::MAX, u64::MAX));
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{Float, GlobalMercator};
+
+/// Summary of one zoom level of a `GlobalMercator` pyramid, as yielded by `zoom_levels`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ZoomLevel<T = f64> {
+    pub zoom: u32,
+    /// Meters per pixel at the equator
+    pub resolution: T,
+    pub scale_denominator: T,
+    /// Tiles along each axis
+    pub num_tiles: u64,
+}
+
+impl<T: Float> GlobalMercator<T> {
+    /// Returns the resolution, scale denominator and tile count of every zoom from `min_zoom` to
+    /// `max_zoom` inclusive, e.g. to print the table of a tile scheme.
+    pub fn zoom_levels(&self, min_zoom: u32, max_zoom: u32) -> impl Iterator<Item = ZoomLevel<T>> {
+        let mercator = *self;
+        (min_zoom..=max_zoom).map(move |zoom| ZoomLevel {
+            zoom,
+            resolution: mercator.resolution(zoom),
+            scale_denominator: mercator.scale_denominator(zoom),
+            num_tiles: mercator.num_tiles(zoom),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoom_levels() {
+        let mercator = GlobalMercator::default();
+        let levels: Vec<ZoomLevel> = mercator.zoom_levels(0, 3).collect();
+
+        assert_eq!(levels.len(), 4);
+        assert_eq!(levels[0].num_tiles, 1);
+        for (level, next) in levels.iter().zip(levels.iter().skip(1)) {
+            assert_eq!(next.zoom, level.zoom + 1);
+            assert_eq!(next.num_tiles, level.num_tiles * 2);
+            assert_eq!(next.resolution, level.resolution / 2.0);
+        }
+        assert_eq!(levels[3].resolution, mercator.resolution(3));
+        assert_eq!(levels[3].scale_denominator, mercator.scale_denominator(3));
+
+        assert_eq!(mercator.zoom_levels(5, 5).count(), 1);
+        assert_eq!(mercator.zoom_levels(5, 4).count(), 0);
+    }
+}
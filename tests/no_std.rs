@@ -0,0 +1,29 @@
+//! Builds the library without `std`, so a regression shows up in `cargo test` and not only in CI.
+
+use std::path::Path;
+use std::process::Command;
+
+fn check(features: &[&str]) {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    // A separate target directory, the one of the running tests is locked by the outer cargo
+    let target_dir = Path::new(manifest_dir).join("target").join("no_std-check");
+
+    let mut command = Command::new(env!("CARGO"));
+    command.current_dir(manifest_dir).arg("check").arg("--lib").arg("--no-default-features").arg("--target-dir").arg(target_dir);
+    if !features.is_empty() {
+        command.arg("--features").arg(features.join(","));
+    }
+
+    let output = command.output().expect("failed to run cargo");
+    assert!(output.status.success(), "cargo check {:?} failed:\n{}", features, String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_no_std() {
+    check(&[]);
+}
+
+#[test]
+fn test_no_std_alloc() {
+    check(&["alloc"]);
+}